@@ -1,4 +1,6 @@
 mod shape_batch;
 pub use shape_batch::*;
+mod texture_batch;
+pub use texture_batch::*;
 mod renderer;
 pub use renderer::*;
@@ -1,24 +1,37 @@
-use cgmath::{ortho, vec2};
+use cgmath::{ortho, vec2, Matrix4, SquareMatrix};
+use gl::types::GLuint;
 
 use crate::{
     error,
-    garlic::{Color, IBatch, QuadGroup, Shader},
+    garlic::{Color, IBatch, QuadGroup, RenderTarget, Shader},
     mem::MutRef,
     warn,
 };
 
-use super::{ShapeBatch, ShapeData, SHAPE_BATCH_ID};
+use super::{ShapeBatch, ShapeData, TextureBatch, TextureData, SHAPE_BATCH_ID, TEXTURE_BATCH_ID};
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 //                                           Batch Type                                           //
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 
-#[derive(Eq, PartialEq)]
+#[derive(Copy, Clone, Eq, PartialEq)]
 enum BatchType {
     ShapeBatch,
+    TextureBatch,
+    Custom(usize),
     None,
 }
 
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                            Draw Call                                           //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+// Shape recorded instead of being batched, used by headless renderers
+pub struct DrawCall {
+    pub shape: ShapeData,
+    pub z_index: i32,
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 //                                           Renderer2D                                           //
 ////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -26,8 +39,16 @@ enum BatchType {
 pub struct Renderer2D {
     batches: Vec<Box<dyn IBatch>>,
     shape_batch_shader: Option<Shader>,
+    texture_batch_shader: Option<Shader>,
+    custom_shaders: Vec<(usize, Shader)>,
     batch_type: BatchType,
     z_index: i32,
+    width: u32,
+    height: u32,
+    // World to viewport pixels & viewport rectangle in the target, set by cameras
+    view: Matrix4<f32>,
+    viewport: (f32, f32, f32, f32),
+    recording: Option<Vec<DrawCall>>,
 }
 
 impl Renderer2D {
@@ -35,53 +56,188 @@ impl Renderer2D {
         return Renderer2D {
             batches: Vec::new(),
             shape_batch_shader: None,
+            texture_batch_shader: None,
+            custom_shaders: Vec::new(),
             batch_type: BatchType::None,
             z_index: 0,
+            width: 0,
+            height: 0,
+            view: Matrix4::identity(),
+            viewport: (0.0, 0.0, 0.0, 0.0),
+            recording: None,
         };
     }
 
     pub fn init(&mut self) {
         if self.shape_batch_shader.is_none() {
             self.shape_batch_shader = Some(Shader::load("res/shaders/shape_batch"));
+            self.texture_batch_shader = Some(Shader::load("res/shaders/texture_batch"));
         } else {
             error!("Carrot", "Renderer2D already initialized!");
         }
     }
 
+    // Record draw calls without touching GL, for headless renderers
+    pub fn start_recording(&mut self) {
+        self.recording = Some(Vec::new());
+    }
+
+    pub fn get_draw_calls(&self) -> &[DrawCall] {
+        return self.recording.as_deref().unwrap_or(&[]);
+    }
+
+    pub fn clear_draw_calls(&mut self) {
+        if let Some(recording) = &mut self.recording {
+            recording.clear();
+        }
+    }
+
+    pub fn is_recording(&self) -> bool {
+        return self.recording.is_some();
+    }
+
+    // Custom batches are rendered with their own shader, which receives the same uniforms as the shape batch
+    pub fn register_batch(&mut self, id: usize, shader_path: &str) {
+        if id == SHAPE_BATCH_ID || id == TEXTURE_BATCH_ID || self.custom_shaders.iter().any(|(custom, _)| *custom == id) {
+            error!("Carrot", "Batch id {} is already registered!", id);
+            return;
+        }
+        if self.is_recording() {
+            return;
+        }
+
+        self.custom_shaders.push((id, Shader::load(shader_path)));
+        self.load_view();
+    }
+
+    // Resets the view to the whole target
     pub fn resize(&mut self, width: u32, height: u32) {
-        let projection = ortho(0f32, width as f32, height as f32, 0f32, 0f32, 1f32);
-
-        self.bind_shader(BatchType::ShapeBatch);
-        self.shape_batch_shader
-            .as_mut()
-            .unwrap()
-            .load_mat4("uProjection", projection);
-        self.shape_batch_shader
-            .as_mut()
-            .unwrap()
-            .load_vec2_f32("uScreenSize", vec2(width as f32, height as f32));
+        self.width = width;
+        self.height = height;
+        self.view = Matrix4::identity();
+        self.viewport = (0.0, 0.0, width as f32, height as f32);
+        self.load_view();
+    }
+
+    // Shapes are drawn in world space through the view into the viewport, pending shapes must be rendered first
+    pub fn set_view(&mut self, view: Matrix4<f32>, viewport: (f32, f32, f32, f32)) {
+        self.view = view;
+        self.viewport = viewport;
+        self.load_view();
+    }
+
+    fn load_view(&mut self) {
+        if self.is_recording() {
+            return;
+        }
+
+        let (width, height) = (self.width, self.height);
+        let (x, y, viewport_width, viewport_height) = self.viewport;
+        let projection = ortho(0f32, viewport_width, viewport_height, 0f32, 0f32, 1f32) * self.view;
+        let inverse_view = self.view.invert().unwrap_or(Matrix4::identity());
+
+        let mut batch_types = vec![BatchType::ShapeBatch, BatchType::TextureBatch];
+        batch_types.extend(self.custom_shaders.iter().map(|(id, _)| BatchType::Custom(*id)));
+        for batch_type in batch_types {
+            self.bind_shader(batch_type);
+            let shader = self.get_shader(batch_type).unwrap();
+            shader.load_mat4("uProjection", projection);
+            // Textured quads don't need the screen size
+            if batch_type != BatchType::TextureBatch {
+                shader.load_vec2_f32("uScreenSize", vec2(width as f32, height as f32));
+            }
+            // Needed by shaders working in pixel space, like the shape batch
+            if shader.has_uniform("uViewOrigin") {
+                shader.load_vec2_f32("uViewOrigin", vec2(x, y));
+            }
+            if shader.has_uniform("uInverseView") {
+                shader.load_mat4("uInverseView", inverse_view);
+            }
+        }
         self.bind_shader(BatchType::None);
     }
 
-    fn push_shape(&mut self, shape: ShapeData) {
+    // Batch with the current z index and free space, created if needed
+    pub fn get_batch<Batch: IBatch + 'static>(&mut self, id: usize) -> MutRef<Batch> {
         for batch in self.batches.iter_mut() {
-            if batch.id() == SHAPE_BATCH_ID
+            if batch.id() == id
                 && (batch.z_index() == self.z_index || batch.is_empty())
                 && batch.has_space()
             {
                 if batch.z_index() != self.z_index {
                     *batch.z_index_mut() = self.z_index;
                 }
-                let shape_batch = batch.as_any_mut().downcast_mut::<ShapeBatch>().unwrap();
-                shape_batch.push(shape);
-                return;
+                return MutRef::from(batch.as_any_mut().downcast_mut::<Batch>().unwrap());
             }
         }
 
-        let mut batch = ShapeBatch::new();
+        let mut batch = Batch::new();
         *batch.z_index_mut() = self.z_index;
-        batch.push(shape);
         self.batches.push(Box::new(batch));
+        return MutRef::from(self.batches.last_mut().unwrap().as_any_mut().downcast_mut::<Batch>().unwrap());
+    }
+
+    fn push_shape(&mut self, shape: ShapeData) {
+        if let Some(recording) = &mut self.recording {
+            recording.push(DrawCall {
+                shape,
+                z_index: self.z_index,
+            });
+            return;
+        }
+
+        self.get_batch::<ShapeBatch>(SHAPE_BATCH_ID).push(shape);
+    }
+
+    fn push_texture(&mut self, texture: GLuint, data: TextureData) {
+        if self.is_recording() {
+            return;
+        }
+
+        // Texture batches are only shared by quads sampling the same texture
+        for batch in self.batches.iter_mut() {
+            if batch.id() == TEXTURE_BATCH_ID
+                && (batch.z_index() == self.z_index || batch.is_empty())
+                && batch.has_space()
+            {
+                let batch = batch.as_any_mut().downcast_mut::<TextureBatch>().unwrap();
+                if batch.get_texture() == texture || batch.is_empty() {
+                    *batch.z_index_mut() = self.z_index;
+                    batch.set_texture(texture);
+                    batch.push(data);
+                    return;
+                }
+            }
+        }
+
+        let mut batch = TextureBatch::new();
+        *batch.z_index_mut() = self.z_index;
+        batch.set_texture(texture);
+        batch.push(data);
+        self.batches.push(Box::new(batch));
+    }
+
+    pub fn draw_texture(&mut self, texture: GLuint, x: f32, y: f32, width: f32, height: f32, tint: Color) {
+        self.push_texture(texture, TextureData {
+            x,
+            y,
+            width,
+            height,
+            uv: (0.0, 0.0, 1.0, 1.0),
+            tint,
+        });
+    }
+
+    // Framebuffer textures are stored bottom up, so the texture is flipped vertically
+    pub fn draw_target(&mut self, target: &RenderTarget, x: f32, y: f32, width: f32, height: f32, tint: Color) {
+        self.push_texture(target.get_texture(), TextureData {
+            x,
+            y,
+            width,
+            height,
+            uv: (0.0, 1.0, 1.0, 0.0),
+            tint,
+        });
     }
 
     pub fn fill_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: Color) {
@@ -96,20 +252,32 @@ impl Renderer2D {
         });
     }
 
+    fn get_shader(&mut self, batch_type: BatchType) -> Option<&mut Shader> {
+        return match batch_type {
+            BatchType::ShapeBatch => self.shape_batch_shader.as_mut(),
+            BatchType::TextureBatch => self.texture_batch_shader.as_mut(),
+            BatchType::Custom(id) => self.custom_shaders.iter_mut().find(|(custom, _)| *custom == id).map(|(_, shader)| shader),
+            BatchType::None => None,
+        };
+    }
+
     fn bind_shader(&mut self, batch_type: BatchType) {
         if batch_type != self.batch_type {
             if self.batch_type != BatchType::None {
                 Shader::unbind();
             }
-            match batch_type {
-                BatchType::ShapeBatch => self.shape_batch_shader.as_mut().unwrap().bind(),
-                BatchType::None => {}
+            if let Some(shader) = self.get_shader(batch_type) {
+                shader.bind();
             }
             self.batch_type = batch_type;
         }
     }
 
     pub fn render(&mut self) {
+        if self.is_recording() {
+            return;
+        }
+
         self.batches.sort();
 
         let mut batches = MutRef::from(&mut self.batches);
@@ -117,6 +285,12 @@ impl Renderer2D {
             if batch.id() == SHAPE_BATCH_ID {
                 self.bind_shader(BatchType::ShapeBatch);
                 batch.render(self.shape_batch_shader.as_mut().unwrap());
+            } else if batch.id() == TEXTURE_BATCH_ID {
+                self.bind_shader(BatchType::TextureBatch);
+                batch.render(self.texture_batch_shader.as_mut().unwrap());
+            } else if self.custom_shaders.iter().any(|(id, _)| *id == batch.id()) {
+                self.bind_shader(BatchType::Custom(batch.id()));
+                batch.render(self.get_shader(BatchType::Custom(batch.id())).unwrap());
             } else {
                 warn!("Carrot", "Unknown batch id {}!", batch.id());
             }
@@ -125,4 +299,22 @@ impl Renderer2D {
         // Unbind Shader
         self.bind_shader(BatchType::None);
     }
+
+    pub fn dispose(&mut self) {
+        self.bind_shader(BatchType::None);
+        for batch in self.batches.iter_mut() {
+            batch.dispose();
+        }
+        self.batches.clear();
+
+        if let Some(shader) = self.shape_batch_shader.take() {
+            shader.dispose();
+        }
+        if let Some(shader) = self.texture_batch_shader.take() {
+            shader.dispose();
+        }
+        for (_, shader) in self.custom_shaders.drain(..) {
+            shader.dispose();
+        }
+    }
 }
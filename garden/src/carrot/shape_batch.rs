@@ -5,7 +5,7 @@ use std::ffi::c_void;
 use std::mem::size_of;
 use std::ptr;
 
-use crate::garlic::{Color, IBatch, QuadGroup, Shader};
+use crate::garlic::{get_gl_registry, Color, GLResource, IBatch, QuadGroup, Shader};
 
 const SHAPE_BATCH_SIZE: usize = 200;
 pub const SHAPE_BATCH_ID: usize = 0x101;
@@ -132,6 +132,11 @@ impl IBatch for ShapeBatch {
             let u_size = [Vector2::<f32>::new(0.0, 0.0); SHAPE_BATCH_SIZE];
             let u_thickness = [0f32; SHAPE_BATCH_SIZE];
 
+            let mut registry = get_gl_registry();
+            registry.track(GLResource::VertexArray(vao), "ShapeBatch");
+            registry.track(GLResource::Buffer(ebo), "ShapeBatch");
+            registry.track(GLResource::Buffer(vbo), "ShapeBatch");
+
             return ShapeBatch {
                 z_index: 0,
                 vao,
@@ -187,6 +192,11 @@ impl IBatch for ShapeBatch {
             gl::DeleteBuffers(1, &self.ebo);
             gl::DeleteVertexArrays(1, &self.vao);
         }
+
+        let mut registry = get_gl_registry();
+        registry.release(GLResource::Buffer(self.vbo));
+        registry.release(GLResource::Buffer(self.ebo));
+        registry.release(GLResource::VertexArray(self.vao));
     }
 
     fn has_space(&self) -> bool {
@@ -0,0 +1,269 @@
+use gl::types::{GLfloat, GLint, GLsizei, GLsizeiptr, GLuint};
+use std::any::Any;
+use std::ffi::c_void;
+use std::mem::size_of;
+use std::ptr;
+
+use crate::garlic::{get_gl_registry, Color, GLResource, IBatch, Shader};
+
+const TEXTURE_BATCH_SIZE: usize = 200;
+pub const TEXTURE_BATCH_ID: usize = 0x102;
+
+// ============ Data Layout =============
+// Position:    2 x f32
+const POSITION_SIZE: usize = 2;
+const POSITION_OFFSET: usize = 0;
+// UV:          2 x f32
+const UV_SIZE: usize = 2;
+const UV_OFFSET: usize = POSITION_OFFSET + POSITION_SIZE * size_of::<GLfloat>();
+// Tint:        4 x f32
+const TINT_SIZE: usize = 4;
+const TINT_OFFSET: usize = UV_OFFSET + UV_SIZE * size_of::<GLfloat>();
+// Total:       8 x f32
+const VERTEX_SIZE: usize = POSITION_SIZE + UV_SIZE + TINT_SIZE;
+const VERTEX_SIZE_BYTES: usize = VERTEX_SIZE * size_of::<GLfloat>();
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                          Texture Data                                          //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct TextureData {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    // Texture coordinates of the top left and bottom right corners
+    pub uv: (f32, f32, f32, f32),
+    pub tint: Color,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                          Texture Batch                                         //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+// Every quad of a batch samples the same texture
+pub struct TextureBatch {
+    z_index: i32,
+    texture: GLuint,
+    vao: GLuint,
+    vbo: GLuint,
+    ebo: GLuint,
+    data: [f32; TEXTURE_BATCH_SIZE * VERTEX_SIZE * 4],
+    count: usize,
+}
+
+impl IBatch for TextureBatch {
+    fn new() -> Self {
+        unsafe {
+            // Create data
+            let data = [0.0; TEXTURE_BATCH_SIZE * VERTEX_SIZE * 4];
+
+            // Generate Vertex Array
+            let mut vao = 0;
+            gl::GenVertexArrays(1, &mut vao);
+            gl::BindVertexArray(vao);
+
+            // Generate indices
+            let mut indices: [i32; 6 * TEXTURE_BATCH_SIZE] = [0; 6 * TEXTURE_BATCH_SIZE];
+            for i in 0..TEXTURE_BATCH_SIZE {
+                let index = (i * 4) as i32;
+                indices[i * 6] = index + 3;
+                indices[i * 6 + 1] = index + 2;
+                indices[i * 6 + 2] = index;
+                indices[i * 6 + 3] = index;
+                indices[i * 6 + 4] = index + 2;
+                indices[i * 6 + 5] = index + 1;
+            }
+
+            // Generate Element Buffer Object
+            let mut ebo = 0;
+            gl::GenBuffers(1, &mut ebo);
+            gl::BindBuffer(gl::ELEMENT_ARRAY_BUFFER, ebo);
+            gl::BufferData(
+                gl::ELEMENT_ARRAY_BUFFER,
+                (TEXTURE_BATCH_SIZE * 6 * size_of::<GLint>()) as GLsizeiptr,
+                indices.as_ptr() as *const c_void,
+                gl::STATIC_DRAW,
+            );
+
+            // Generate Vertex Buffer Object
+            let mut vbo = 0;
+            gl::GenBuffers(1, &mut vbo);
+            gl::BindBuffer(gl::ARRAY_BUFFER, vbo);
+            gl::BufferData(
+                gl::ARRAY_BUFFER,
+                (VERTEX_SIZE_BYTES * 4 * TEXTURE_BATCH_SIZE) as GLsizeiptr,
+                data.as_ptr() as *const c_void,
+                gl::DYNAMIC_DRAW,
+            );
+
+            // Bind Position Attribute
+            gl::VertexAttribPointer(
+                0,
+                POSITION_SIZE as GLint,
+                gl::FLOAT,
+                gl::FALSE,
+                VERTEX_SIZE_BYTES as GLsizei,
+                POSITION_OFFSET as *const c_void,
+            );
+            gl::EnableVertexAttribArray(0);
+
+            // Bind UV Attribute
+            gl::VertexAttribPointer(
+                1,
+                UV_SIZE as GLint,
+                gl::FLOAT,
+                gl::FALSE,
+                VERTEX_SIZE_BYTES as GLsizei,
+                UV_OFFSET as *const c_void,
+            );
+            gl::EnableVertexAttribArray(1);
+
+            // Bind Tint Attribute
+            gl::VertexAttribPointer(
+                2,
+                TINT_SIZE as GLint,
+                gl::FLOAT,
+                gl::FALSE,
+                VERTEX_SIZE_BYTES as GLsizei,
+                TINT_OFFSET as *const c_void,
+            );
+            gl::EnableVertexAttribArray(2);
+
+            // Unbind Everything
+            gl::BindBuffer(gl::ARRAY_BUFFER, 0);
+            gl::BindVertexArray(0);
+
+            let mut registry = get_gl_registry();
+            registry.track(GLResource::VertexArray(vao), "TextureBatch");
+            registry.track(GLResource::Buffer(ebo), "TextureBatch");
+            registry.track(GLResource::Buffer(vbo), "TextureBatch");
+
+            return TextureBatch {
+                z_index: 0,
+                texture: 0,
+                vao,
+                vbo,
+                ebo,
+                data,
+                count: 0,
+            };
+        }
+    }
+
+    fn render(&mut self, shader: &mut Shader) {
+        unsafe {
+            // Load data
+            gl::BindBuffer(gl::ARRAY_BUFFER, self.vbo);
+            gl::BufferSubData(
+                gl::ARRAY_BUFFER,
+                0,
+                (self.count * VERTEX_SIZE_BYTES * 4) as GLsizeiptr,
+                &self.data[0] as *const f32 as *const c_void,
+            );
+
+            // Bind Texture
+            gl::ActiveTexture(gl::TEXTURE0);
+            gl::BindTexture(gl::TEXTURE_2D, self.texture);
+            shader.load_i32("uTexture", 0);
+
+            // Render Batch
+            gl::BindVertexArray(self.vao);
+            gl::EnableVertexAttribArray(0);
+            gl::EnableVertexAttribArray(1);
+            gl::EnableVertexAttribArray(2);
+            gl::DrawElements(
+                gl::TRIANGLES,
+                (self.count * 6) as GLsizei,
+                gl::UNSIGNED_INT,
+                ptr::null(),
+            );
+            gl::DisableVertexAttribArray(0);
+            gl::DisableVertexAttribArray(1);
+            gl::DisableVertexAttribArray(2);
+            gl::BindVertexArray(0);
+            gl::BindTexture(gl::TEXTURE_2D, 0);
+        }
+
+        self.count = 0;
+    }
+
+    fn dispose(&mut self) {
+        unsafe {
+            gl::BindVertexArray(0);
+            gl::DeleteBuffers(1, &self.vbo);
+            gl::DeleteBuffers(1, &self.ebo);
+            gl::DeleteVertexArrays(1, &self.vao);
+        }
+
+        let mut registry = get_gl_registry();
+        registry.release(GLResource::Buffer(self.vbo));
+        registry.release(GLResource::Buffer(self.ebo));
+        registry.release(GLResource::VertexArray(self.vao));
+    }
+
+    fn has_space(&self) -> bool {
+        return self.count < TEXTURE_BATCH_SIZE;
+    }
+
+    fn is_empty(&self) -> bool {
+        return self.count == 0;
+    }
+
+    fn z_index(&self) -> i32 {
+        return self.z_index;
+    }
+
+    fn z_index_mut(&mut self) -> &mut i32 {
+        return &mut self.z_index;
+    }
+
+    fn id(&self) -> usize {
+        return TEXTURE_BATCH_ID;
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+impl TextureBatch {
+    pub fn push(&mut self, data: TextureData) {
+        let (u0, v0, u1, v1) = data.uv;
+        // Same vertex order as the shape batch: bottom right, top right, top left, bottom left
+        let vertices = [
+            (data.x + data.width, data.y + data.height, u1, v1),
+            (data.x + data.width, data.y, u1, v0),
+            (data.x, data.y, u0, v0),
+            (data.x, data.y + data.height, u0, v1),
+        ];
+
+        // Push VBO Data
+        let mut ptr = self.count * VERTEX_SIZE * 4;
+        for (x, y, u, v) in vertices {
+            self.data[ptr] = x;
+            self.data[ptr + 1] = y;
+            self.data[ptr + 2] = u;
+            self.data[ptr + 3] = v;
+            self.data[ptr + 4] = data.tint.red;
+            self.data[ptr + 5] = data.tint.green;
+            self.data[ptr + 6] = data.tint.blue;
+            self.data[ptr + 7] = data.tint.alpha;
+
+            ptr += VERTEX_SIZE;
+        }
+
+        self.count += 1;
+    }
+
+    pub fn get_texture(&self) -> GLuint {
+        return self.texture;
+    }
+
+    // Only allowed while the batch is empty
+    pub fn set_texture(&mut self, texture: GLuint) {
+        if self.is_empty() {
+            self.texture = texture;
+        }
+    }
+}
\ No newline at end of file
@@ -24,7 +24,7 @@ impl EffectMode {
                     2.0 * linear * linear
                 } else {
                     linear -= 1.0;
-                    -2.0 * (linear - 1.0) * (linear - 1.0)
+                    1.0 - 2.0 * linear * linear
                 }
             },
         }
@@ -97,7 +97,7 @@ impl<T: Interpolate<O, T> + Clone, O: Clone> Effect<T, O> {
     }
 
     pub fn update(&mut self) {
-        let now = App::get().window().get_time(); 
+        let now = App::get().time().get_time();
         if let (EffectState::Started(start_time), Some(target)) = (self.state, &mut self.target) {
             if now >= start_time + self.duration {
                 if self.repeat {
@@ -112,6 +112,11 @@ impl<T: Interpolate<O, T> + Clone, O: Clone> Effect<T, O> {
                 let value = self.mode.calculate(self.duration, now - start_time);
                 self.current = T::interpolate(self.start.clone(), target.clone(), value);
             }
+
+            // Keep rendering while the animation is running, paused time freezes it
+            if let (EffectState::Started(_), false) = (self.state, App::get().time().is_paused()) {
+                App::get().request_redraw();
+            }
         } else if let EffectState::Started(_) = self.state {
             self.state = EffectState::Stopped;
         }
@@ -139,8 +144,9 @@ impl<T: Interpolate<O, T> + Clone, O: Clone> Effect<T, O> {
     }
 
     pub fn start(&mut self) -> &mut Effect<T, O> {
-        let now = App::get().window().get_time(); 
+        let now = App::get().time().get_time();
         self.state = EffectState::Started(now);
+        App::get().request_redraw();
         return self;
     }
 
@@ -153,4 +159,36 @@ impl<T: Interpolate<O, T> + Clone, O: Clone> Effect<T, O> {
         return self.current.clone();
     }
 
+    pub fn is_running(&self) -> bool {
+        return matches!(self.state, EffectState::Started(_));
+    }
+
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                              Tests                                             //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ease_in_out_is_continuous() {
+        let mut mode = EffectMode::EaseInOut;
+        assert_eq!(mode.calculate(1.0, 0.0), 0.0);
+        assert_eq!(mode.calculate(1.0, 0.25), 0.125);
+        assert_eq!(mode.calculate(1.0, 0.5), 0.5);
+        assert_eq!(mode.calculate(1.0, 0.75), 0.875);
+        assert_eq!(mode.calculate(1.0, 1.0), 1.0);
+
+        // Never leaves [0, 1] and never goes backwards
+        let mut last = 0.0;
+        for step in 0..=100 {
+            let value = mode.calculate(2.0, step as f64 / 50.0);
+            assert!((0.0..=1.0).contains(&value) && value >= last);
+            last = value;
+        }
+    }
+
 }
\ No newline at end of file
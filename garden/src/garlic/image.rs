@@ -0,0 +1,83 @@
+use std::fs;
+use std::io;
+
+use crate::critical;
+
+use super::{png, Color};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                              Image                                             //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+// 8 bit RGBA pixels, rows top to bottom
+#[derive(Clone)]
+pub struct Image {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Image {
+
+    pub fn new(width: u32, height: u32) -> Image {
+        return Image {
+            width,
+            height,
+            pixels: vec![0; width as usize * height as usize * 4],
+        };
+    }
+
+    pub fn from_pixels(width: u32, height: u32, pixels: Vec<u8>) -> Image {
+        if pixels.len() != width as usize * height as usize * 4 {
+            critical!("Garlic", "Image ({}x{}) expects {} bytes, got {}!", width, height, width as usize * height as usize * 4, pixels.len());
+        }
+        return Image {
+            width,
+            height,
+            pixels,
+        };
+    }
+
+    // GL reads pixels bottom up
+    pub fn flip_vertically(&mut self) {
+        let stride = self.width as usize * 4;
+        let height = self.height as usize;
+        for row in 0..height / 2 {
+            let (top, bottom) = self.pixels.split_at_mut((height - row - 1) * stride);
+            top[row * stride..(row + 1) * stride].swap_with_slice(&mut bottom[..stride]);
+        }
+    }
+
+    pub fn encode_png(&self) -> Vec<u8> {
+        return png::encode(self.width, self.height, &self.pixels);
+    }
+
+    // Stored without compression, see png::encode
+    pub fn save_png(&self, path: &str) -> io::Result<()> {
+        return fs::write(path, self.encode_png());
+    }
+
+    // Getters
+    pub fn get_pixel(&self, x: u32, y: u32) -> Color {
+        let index = (y as usize * self.width as usize + x as usize) * 4;
+        return Color::from_rgba255(
+            self.pixels[index] as i32,
+            self.pixels[index + 1] as i32,
+            self.pixels[index + 2] as i32,
+            self.pixels[index + 3] as i32,
+        );
+    }
+
+    pub fn get_pixels(&self) -> &[u8] {
+        return &self.pixels;
+    }
+
+    pub fn get_width(&self) -> u32 {
+        return self.width;
+    }
+
+    pub fn get_height(&self) -> u32 {
+        return self.height;
+    }
+
+}
\ No newline at end of file
@@ -9,4 +9,11 @@ pub use effect::*;
 mod shader;
 pub use shader::*;
 mod quad_group;
-pub use quad_group::*;
\ No newline at end of file
+pub use quad_group::*;
+mod registry;
+pub use registry::*;
+mod target;
+pub use target::*;
+mod image;
+pub use image::*;
+mod png;
\ No newline at end of file
@@ -0,0 +1,163 @@
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                           PNG Encoder                                          //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+// Uncompressed zlib stream, screenshots favour speed over size:
+// files weigh about width * height * 4 bytes, 8 MB for a 1080p frame
+const MAX_STORED_BLOCK: usize = 65535;
+
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+
+// 8 bit RGBA, rows top to bottom
+pub fn encode(width: u32, height: u32, pixels: &[u8]) -> Vec<u8> {
+    let stride = width as usize * 4;
+
+    // Every row is prefixed with its filter type, 0 = None
+    let mut raw = Vec::with_capacity((stride + 1) * height as usize);
+    for row in pixels.chunks(stride).take(height as usize) {
+        raw.push(0);
+        raw.extend_from_slice(row);
+    }
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&width.to_be_bytes());
+    header.extend_from_slice(&height.to_be_bytes());
+    // Bit depth, color type (RGBA), compression, filter & interlace methods
+    header.extend_from_slice(&[8, 6, 0, 0, 0]);
+
+    let mut png = Vec::with_capacity(raw.len() + raw.len() / MAX_STORED_BLOCK * 5 + 64);
+    png.extend_from_slice(&SIGNATURE);
+    write_chunk(&mut png, b"IHDR", &header);
+    write_chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+    write_chunk(&mut png, b"IEND", &[]);
+    return png;
+}
+
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    // The CRC covers the chunk type & data
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut stream = Vec::with_capacity(data.len() + data.len() / MAX_STORED_BLOCK * 5 + 11);
+    // Deflate, 32K window, no preset dictionary
+    stream.extend_from_slice(&[0x78, 0x01]);
+
+    let mut blocks = data.chunks(MAX_STORED_BLOCK).peekable();
+    if blocks.peek().is_none() {
+        stream.extend_from_slice(&[1, 0, 0, 0xFF, 0xFF]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let len = block.len() as u16;
+        stream.push(if last { 1 } else { 0 });
+        stream.extend_from_slice(&len.to_le_bytes());
+        stream.extend_from_slice(&(!len).to_le_bytes());
+        stream.extend_from_slice(block);
+    }
+
+    stream.extend_from_slice(&adler32(data).to_be_bytes());
+    return stream;
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut table = [0u32; 256];
+    for (n, entry) in table.iter_mut().enumerate() {
+        let mut c = n as u32;
+        for _ in 0..8 {
+            c = if c & 1 != 0 { 0xEDB88320 ^ (c >> 1) } else { c >> 1 };
+        }
+        *entry = c;
+    }
+
+    let mut crc = 0xFFFFFFFFu32;
+    for byte in data {
+        crc = table[((crc ^ *byte as u32) & 0xFF) as usize] ^ (crc >> 8);
+    }
+    return crc ^ 0xFFFFFFFF;
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let mut a = 1u32;
+    let mut b = 0u32;
+    // Largest chunk before the sums can overflow
+    for chunk in data.chunks(5552) {
+        for byte in chunk {
+            a += *byte as u32;
+            b += a;
+        }
+        a %= 65521;
+        b %= 65521;
+    }
+    return (b << 16) | a;
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                              Tests                                             //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn be32(bytes: &[u8]) -> u32 {
+        return u32::from_be_bytes(bytes[..4].try_into().unwrap());
+    }
+
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(b"123456789"), 0xCBF43926);
+        assert_eq!(crc32(b"IEND"), 0xAE426082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11E60398);
+        assert_eq!(adler32(&[]), 1);
+        // Spans several 5552 byte chunks
+        let data: Vec<u8> = (0..76800).map(|i| i as u8).collect();
+        assert_eq!(adler32(&data), 0x09F872BC);
+    }
+
+    #[test]
+    fn encode_small_image() {
+        let png = encode(2, 1, &[255, 0, 0, 255, 0, 255, 0, 128]);
+        assert_eq!(png[..8], SIGNATURE);
+
+        // IHDR: 2x1, 8 bit RGBA
+        assert_eq!(be32(&png[8..]), 13);
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(png[16..29], [0, 0, 0, 2, 0, 0, 0, 1, 8, 6, 0, 0, 0]);
+        assert_eq!(be32(&png[29..]), 0xF4227F8A);
+
+        // IDAT: zlib header, a single final stored block, the filtered rows & the adler trailer
+        assert_eq!(be32(&png[33..]), 20);
+        assert_eq!(&png[37..41], b"IDAT");
+        let stream = &png[41..61];
+        assert_eq!(stream[..2], [0x78, 0x01]);
+        assert_eq!(stream[2..7], [1, 9, 0, 0xF6, 0xFF]);
+        assert_eq!(stream[7..16], [0, 255, 0, 0, 255, 0, 255, 0, 128]);
+        assert_eq!(be32(&stream[16..]), 0x1079037E);
+        assert_eq!(be32(&png[61..]), 0x36A06860);
+
+        // IEND
+        assert_eq!(png[65..], [0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xAE, 0x42, 0x60, 0x82]);
+    }
+
+    #[test]
+    fn split_stored_blocks() {
+        let data = vec![7u8; MAX_STORED_BLOCK + 10];
+        let stream = zlib_stored(&data);
+        assert_eq!(stream.len(), 2 + 5 + MAX_STORED_BLOCK + 5 + 10 + 4);
+        // First block isn't final, the second one is
+        assert_eq!(stream[2..7], [0, 0xFF, 0xFF, 0, 0]);
+        let second = 7 + MAX_STORED_BLOCK;
+        assert_eq!(stream[second..second + 5], [1, 10, 0, 0xF5, 0xFF]);
+        assert_eq!(be32(&stream[stream.len() - 4..]), 0x084C00A9);
+
+        // Empty data still needs a final block
+        assert_eq!(zlib_stored(&[]), [0x78, 0x01, 1, 0, 0, 0xFF, 0xFF, 0, 0, 0, 1]);
+    }
+
+}
\ No newline at end of file
@@ -0,0 +1,167 @@
+use std::ptr::addr_of_mut;
+
+use gl::types::GLuint;
+
+use crate::{mem::MutRef, debug, warn};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                           GL Resource                                          //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum GLResource {
+    VertexArray(GLuint),
+    Buffer(GLuint),
+    Shader(GLuint),
+    Program(GLuint),
+    Texture(GLuint),
+    Framebuffer(GLuint),
+    Renderbuffer(GLuint),
+}
+
+impl GLResource {
+
+    // Vertex arrays and framebuffers are not shared between contexts
+    fn is_shared(&self) -> bool {
+        return !matches!(self, GLResource::VertexArray(_) | GLResource::Framebuffer(_));
+    }
+
+    fn delete(&self) {
+        unsafe {
+            match self {
+                GLResource::VertexArray(id) => gl::DeleteVertexArrays(1, id),
+                GLResource::Buffer(id) => gl::DeleteBuffers(1, id),
+                GLResource::Shader(id) => gl::DeleteShader(*id),
+                GLResource::Program(id) => gl::DeleteProgram(*id),
+                GLResource::Texture(id) => gl::DeleteTextures(1, id),
+                GLResource::Framebuffer(id) => gl::DeleteFramebuffers(1, id),
+                GLResource::Renderbuffer(id) => gl::DeleteRenderbuffers(1, id),
+            }
+        }
+    }
+
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                           GL Registry                                          //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+static mut REGISTRY: Option<GLRegistry> = None;
+
+pub fn get_gl_registry() -> MutRef<GLRegistry> {
+    unsafe {
+        return MutRef::from((*addr_of_mut!(REGISTRY)).get_or_insert_with(GLRegistry::new));
+    }
+}
+
+struct TrackedResource {
+    resource: GLResource,
+    owner: String,
+    // Window owning the context of a per-context object, None for the main window and shared objects
+    context: Option<String>,
+}
+
+// Tracks live GL objects in creation order, owners release them when disposing
+pub struct GLRegistry {
+    resources: Vec<TrackedResource>,
+    context: Option<String>,
+}
+
+impl GLRegistry {
+
+    fn new() -> GLRegistry {
+        return GLRegistry {
+            resources: Vec::new(),
+            context: None,
+        }
+    }
+
+    // Sets the window whose context is current, None for the main window
+    pub fn set_context(&mut self, context: Option<String>) {
+        self.context = context;
+    }
+
+    fn context_of(&self, resource: GLResource) -> Option<String> {
+        return if resource.is_shared() { None } else { self.context.clone() };
+    }
+
+    pub fn track<Str: ToString>(&mut self, resource: GLResource, owner: Str) {
+        let context = self.context_of(resource);
+        self.resources.push(TrackedResource { resource, owner: owner.to_string(), context });
+    }
+
+    pub fn release(&mut self, resource: GLResource) {
+        let context = self.context_of(resource);
+        if let Some(index) = self.resources.iter().rposition(|tracked| tracked.resource == resource && tracked.context == context) {
+            self.resources.remove(index);
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        return self.resources.len();
+    }
+
+    // Destroys the objects still alive in a secondary window's context, must run while it is current
+    pub fn dispose_context(&mut self, context: &str) {
+        let mut count = 0;
+        while let Some(index) = self.resources.iter().rposition(|tracked| tracked.context.as_deref() == Some(context)) {
+            let tracked = self.resources.remove(index);
+            if cfg!(debug_assertions) {
+                warn!("Garlic", "Leaked {:?} created by '{}' in window '{}'!", tracked.resource, tracked.owner, context);
+            }
+            tracked.resource.delete();
+            count += 1;
+        }
+        debug!("Garlic", "Destroyed {} remaining GL objects of window '{}'", count, context);
+    }
+
+    // Destroys everything still alive in reverse creation order, must run with the main context current before it is dropped
+    pub fn dispose_all(&mut self) {
+        if cfg!(debug_assertions) {
+            for tracked in self.resources.iter() {
+                warn!("Garlic", "Leaked {:?} created by '{}'!", tracked.resource, tracked.owner);
+            }
+        }
+
+        let count = self.resources.len();
+        while let Some(tracked) = self.resources.pop() {
+            // Objects of a destroyed context went away with it
+            if tracked.context.is_none() {
+                tracked.resource.delete();
+            }
+        }
+        debug!("Garlic", "Destroyed {} remaining GL objects", count);
+    }
+
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                              Tests                                             //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn per_context_objects_are_keyed_by_window() {
+        let mut registry = GLRegistry::new();
+        registry.track(GLResource::VertexArray(1), "Main");
+        registry.track(GLResource::Texture(1), "Main");
+
+        // The same ids in a secondary context don't collide with the main window's
+        registry.set_context(Some("Tools".to_string()));
+        registry.track(GLResource::VertexArray(1), "Tools");
+        registry.track(GLResource::Texture(2), "Tools");
+        registry.set_context(None);
+        registry.release(GLResource::VertexArray(1));
+        assert_eq!(registry.count(), 3);
+        assert_eq!(registry.resources[1].context, Some("Tools".to_string()));
+
+        // Shared objects are global whichever context is current
+        registry.release(GLResource::Texture(2));
+        assert_eq!(registry.count(), 2);
+        assert_eq!(registry.resources[0].resource, GLResource::Texture(1));
+    }
+
+}
\ No newline at end of file
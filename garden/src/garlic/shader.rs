@@ -6,7 +6,7 @@ use gl::types::{GLchar, GLenum, GLint, GLsizei, GLuint};
 
 use crate::{error, info};
 
-use super::Color;
+use super::{Color, GLResource, get_gl_registry};
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 //                                             Shader                                             //
@@ -23,10 +23,20 @@ pub struct Shader {
 impl Shader {
 
     pub fn load<Str: ToString>(path: Str) -> Shader {
+        let path = path.to_string();
+        return Self::load_program(format!("{}/VS.glsl", path), format!("{}/FS.glsl", path), path);
+    }
+
+    // Shaders that don't follow the VS.glsl / FS.glsl layout, such as post effects sharing a vertex shader
+    pub fn load_files<Str: ToString>(vertex: Str, fragment: Str) -> Shader {
+        return Self::load_program(vertex.to_string(), fragment.to_string(), fragment.to_string());
+    }
+
+    fn load_program(vertex: String, fragment: String, path: String) -> Shader {
         unsafe {
             // Compile the vertex & fragment shader
-            let vertex_id = Self::compile_shader(format!("{}/VS.glsl", path.to_string()), gl::VERTEX_SHADER);
-            let fragment_id = Self::compile_shader(format!("{}/FS.glsl", path.to_string()), gl::FRAGMENT_SHADER);
+            let vertex_id = Self::compile_shader(vertex, gl::VERTEX_SHADER);
+            let fragment_id = Self::compile_shader(fragment, gl::FRAGMENT_SHADER);
 
             // Create the program & Attach the shaders
             let program_id = gl::CreateProgram();
@@ -61,6 +71,11 @@ impl Shader {
 
             info!("Garlic", "Shader '{}' loaded!", path.to_string());
 
+            let mut registry = get_gl_registry();
+            registry.track(GLResource::Shader(vertex_id), path.to_string());
+            registry.track(GLResource::Shader(fragment_id), path.to_string());
+            registry.track(GLResource::Program(program_id), path.to_string());
+
             return Shader {
                 path: path.to_string(),
                 program_id,
@@ -107,6 +122,11 @@ impl Shader {
             gl::DeleteShader(self.fragment_id);
             gl::DeleteProgram(self.program_id);
         }
+
+        let mut registry = get_gl_registry();
+        registry.release(GLResource::Program(self.program_id));
+        registry.release(GLResource::Shader(self.fragment_id));
+        registry.release(GLResource::Shader(self.vertex_id));
     }
 
     pub fn bind(&self) {
@@ -135,6 +155,21 @@ impl Shader {
         }
     }
 
+    // Unlike the load functions, a missing uniform is not an error
+    pub fn has_uniform<Str: ToString>(&mut self, name: Str) -> bool {
+        let name = name.to_string();
+        if let Some(uniform) = self.uniforms.get(&name) {
+            return *uniform != -1;
+        }
+
+        unsafe {
+            let name_c_str = CString::new(name.clone()).unwrap();
+            let location = gl::GetUniformLocation(self.program_id, name_c_str.as_ptr());
+            self.uniforms.insert(name, location);
+            return location != -1;
+        }
+    }
+
     pub fn load_i32<Str: ToString>(&mut self, name: Str, value: i32) {
         unsafe {
             let location = self.get_uniform_location(name.to_string());
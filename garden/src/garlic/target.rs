@@ -0,0 +1,198 @@
+use std::ptr;
+
+use gl::types::{GLint, GLuint};
+
+use crate::error;
+
+use super::{get_gl_registry, GLResource};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                          Render Target                                         //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct RenderTarget {
+    // Single sampled framebuffer, holds the texture
+    framebuffer: GLuint,
+    texture: GLuint,
+    // Multisampled framebuffer, resolved into the texture
+    samples: u32,
+    ms_framebuffer: GLuint,
+    ms_renderbuffer: GLuint,
+    width: u32,
+    height: u32,
+}
+
+impl RenderTarget {
+
+    pub fn new(width: u32, height: u32) -> RenderTarget {
+        return RenderTarget::with_samples(width, height, 1);
+    }
+
+    // Samples > 1 renders with MSAA, call resolve() before using the texture
+    pub fn with_samples(width: u32, height: u32, samples: u32) -> RenderTarget {
+        let mut target = RenderTarget {
+            framebuffer: 0,
+            texture: 0,
+            samples: samples.max(1),
+            ms_framebuffer: 0,
+            ms_renderbuffer: 0,
+            width,
+            height,
+        };
+        target.create();
+        return target;
+    }
+
+    fn create(&mut self) {
+        unsafe {
+            // Generate the color attachment
+            gl::GenTextures(1, &mut self.texture);
+            gl::BindTexture(gl::TEXTURE_2D, self.texture);
+            gl::TexImage2D(
+                gl::TEXTURE_2D,
+                0,
+                gl::RGBA8 as GLint,
+                self.width as i32,
+                self.height as i32,
+                0,
+                gl::RGBA,
+                gl::UNSIGNED_BYTE,
+                ptr::null(),
+            );
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MIN_FILTER, gl::LINEAR as GLint);
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAG_FILTER, gl::LINEAR as GLint);
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_S, gl::CLAMP_TO_EDGE as GLint);
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_T, gl::CLAMP_TO_EDGE as GLint);
+            gl::BindTexture(gl::TEXTURE_2D, 0);
+
+            // Generate the framebuffer
+            gl::GenFramebuffers(1, &mut self.framebuffer);
+            gl::BindFramebuffer(gl::FRAMEBUFFER, self.framebuffer);
+            gl::FramebufferTexture2D(gl::FRAMEBUFFER, gl::COLOR_ATTACHMENT0, gl::TEXTURE_2D, self.texture, 0);
+            if gl::CheckFramebufferStatus(gl::FRAMEBUFFER) != gl::FRAMEBUFFER_COMPLETE {
+                error!("Garlic", "Render target ({}x{}) is incomplete!", self.width, self.height);
+            }
+
+            // Generate the multisampled framebuffer
+            if self.samples > 1 {
+                gl::GenRenderbuffers(1, &mut self.ms_renderbuffer);
+                gl::BindRenderbuffer(gl::RENDERBUFFER, self.ms_renderbuffer);
+                gl::RenderbufferStorageMultisample(gl::RENDERBUFFER, self.samples as i32, gl::RGBA8, self.width as i32, self.height as i32);
+                gl::BindRenderbuffer(gl::RENDERBUFFER, 0);
+
+                gl::GenFramebuffers(1, &mut self.ms_framebuffer);
+                gl::BindFramebuffer(gl::FRAMEBUFFER, self.ms_framebuffer);
+                gl::FramebufferRenderbuffer(gl::FRAMEBUFFER, gl::COLOR_ATTACHMENT0, gl::RENDERBUFFER, self.ms_renderbuffer);
+                if gl::CheckFramebufferStatus(gl::FRAMEBUFFER) != gl::FRAMEBUFFER_COMPLETE {
+                    error!("Garlic", "Render target ({}x{}, {} samples) is incomplete!", self.width, self.height, self.samples);
+                }
+            }
+            gl::BindFramebuffer(gl::FRAMEBUFFER, 0);
+        }
+
+        let mut registry = get_gl_registry();
+        registry.track(GLResource::Texture(self.texture), "RenderTarget");
+        registry.track(GLResource::Framebuffer(self.framebuffer), "RenderTarget");
+        if self.samples > 1 {
+            registry.track(GLResource::Renderbuffer(self.ms_renderbuffer), "RenderTarget");
+            registry.track(GLResource::Framebuffer(self.ms_framebuffer), "RenderTarget");
+        }
+    }
+
+    pub fn resize(&mut self, width: u32, height: u32) {
+        if width != self.width || height != self.height {
+            self.dispose();
+            self.width = width;
+            self.height = height;
+            self.create();
+        }
+    }
+
+    pub fn bind(&self) {
+        unsafe {
+            gl::BindFramebuffer(gl::FRAMEBUFFER, if self.samples > 1 { self.ms_framebuffer } else { self.framebuffer });
+            gl::Viewport(0, 0, self.width as i32, self.height as i32);
+        }
+    }
+
+    pub fn unbind() {
+        unsafe {
+            gl::BindFramebuffer(gl::FRAMEBUFFER, 0);
+        }
+    }
+
+    // Copy the multisampled image into the texture
+    pub fn resolve(&self) {
+        if self.samples <= 1 {
+            return;
+        }
+
+        unsafe {
+            gl::BindFramebuffer(gl::READ_FRAMEBUFFER, self.ms_framebuffer);
+            gl::BindFramebuffer(gl::DRAW_FRAMEBUFFER, self.framebuffer);
+            gl::BlitFramebuffer(
+                0, 0, self.width as i32, self.height as i32,
+                0, 0, self.width as i32, self.height as i32,
+                gl::COLOR_BUFFER_BIT,
+                gl::NEAREST,
+            );
+            gl::BindFramebuffer(gl::FRAMEBUFFER, 0);
+        }
+    }
+
+    // Copy the resolved image onto the default framebuffer, stretched to the given size
+    pub fn blit_to_screen(&self, width: u32, height: u32) {
+        unsafe {
+            gl::BindFramebuffer(gl::READ_FRAMEBUFFER, self.framebuffer);
+            gl::BindFramebuffer(gl::DRAW_FRAMEBUFFER, 0);
+            gl::BlitFramebuffer(
+                0, 0, self.width as i32, self.height as i32,
+                0, 0, width as i32, height as i32,
+                gl::COLOR_BUFFER_BIT,
+                gl::LINEAR,
+            );
+            gl::BindFramebuffer(gl::FRAMEBUFFER, 0);
+        }
+    }
+
+    pub fn dispose(&mut self) {
+        unsafe {
+            gl::DeleteFramebuffers(1, &self.framebuffer);
+            gl::DeleteTextures(1, &self.texture);
+            if self.samples > 1 {
+                gl::DeleteFramebuffers(1, &self.ms_framebuffer);
+                gl::DeleteRenderbuffers(1, &self.ms_renderbuffer);
+            }
+        }
+
+        let mut registry = get_gl_registry();
+        if self.samples > 1 {
+            registry.release(GLResource::Framebuffer(self.ms_framebuffer));
+            registry.release(GLResource::Renderbuffer(self.ms_renderbuffer));
+        }
+        registry.release(GLResource::Framebuffer(self.framebuffer));
+        registry.release(GLResource::Texture(self.texture));
+        self.framebuffer = 0;
+        self.texture = 0;
+        self.ms_framebuffer = 0;
+        self.ms_renderbuffer = 0;
+    }
+
+    // Getters
+    pub fn get_texture(&self) -> GLuint {
+        return self.texture;
+    }
+
+    pub fn get_samples(&self) -> u32 {
+        return self.samples;
+    }
+
+    pub fn get_width(&self) -> u32 {
+        return self.width;
+    }
+
+    pub fn get_height(&self) -> u32 {
+        return self.height;
+    }
+
+}
\ No newline at end of file
@@ -44,5 +44,12 @@ pub mod app {
     pub use crate::onion::*;
 }
 
+#[cfg(feature = "scripting")]
+mod radish;
+#[cfg(feature = "scripting")]
+pub mod scripting {
+    pub use crate::radish::*;
+}
+
 pub use gl as gl;
 pub use cgmath as cgmath;
\ No newline at end of file
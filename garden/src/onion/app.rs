@@ -1,4 +1,6 @@
-use crate::{potato::{LogLevel, Logger}, window::{WindowConfig, Window}, onion::{StateManager, Renderer}, mem::MutRef, critical, ui::{get_system, ScreenProperty, prepare_elements, update_scene}, debug::get_errors};
+use std::{any::{Any, TypeId}, collections::HashMap};
+
+use crate::{potato::{LogLevel, Logger}, window::{post_empty_event, WindowConfig, Window, WindowGeometry}, onion::{StateManager, Renderer, AppWindow, IState, FrameLimiter, FrameStats, Plugin, FramePhase, TaskScheduler, Timers, Time}, mem::MutRef, garlic::get_gl_registry, critical, warn, ui::{get_system, ScreenProperty, prepare_elements, update_scene, update_window_scene}, debug::get_errors};
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 //                                           Update Cap                                           //
@@ -7,26 +9,63 @@ use crate::{potato::{LogLevel, Logger}, window::{WindowConfig, Window}, onion::{
 pub enum UpdateCap {
     Cap(u32),
     Vsync,
-    Unlimited
+    Unlimited,
+    // Only redraws on input, running animations or App::request_redraw()
+    OnDemand
+}
+
+// Maximum time (in seconds) spent waiting for events in OnDemand mode
+const ON_DEMAND_TIMEOUT: f64 = 0.5;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                         Fixed Timestep                                         //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Copy, Clone)]
+pub struct FixedTimestep {
+    // Ticks per second
+    pub rate: u32,
+    // Maximum number of catch-up ticks per frame
+    pub max_steps: u32
+}
+
+impl FixedTimestep {
+
+    pub fn new(rate: u32) -> FixedTimestep {
+        return FixedTimestep {
+            rate,
+            max_steps: 5
+        }
+    }
+
+    pub fn get_delta(&self) -> f64 {
+        return 1.0 / self.rate.max(1) as f64;
+    }
+
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 //                                        App Configuration                                       //
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 
-pub struct AppConfiguration<'a> {
+pub struct AppConfiguration {
     pub update_cap: UpdateCap,
     pub log_level: LogLevel,
-    pub window_config: WindowConfig<'a>
+    pub window_config: WindowConfig,
+    pub fixed_timestep: Option<FixedTimestep>,
+    // Saves the window geometry on close & restores it on setup
+    pub geometry_file: Option<String>
 }
 
-impl<'a> Default for AppConfiguration<'a> {
+impl Default for AppConfiguration {
 
     fn default() -> Self {
         return AppConfiguration {
             update_cap: UpdateCap::Vsync,
             log_level: LogLevel::Info,
-            window_config: WindowConfig::default()
+            window_config: WindowConfig::default(),
+            fixed_timestep: None,
+            geometry_file: None
         }
     }
 
@@ -38,11 +77,30 @@ impl<'a> Default for AppConfiguration<'a> {
 
 static mut APP: Option<App> = None;
 
+// Frame phase callbacks, called with the delta or the render alpha
+type PhaseCallback = Box<dyn FnMut(f64)>;
+
 pub struct App {
     pub window: Option<Window>,
+    pub windows: Vec<Box<AppWindow>>,
     pub state_manager: StateManager,
+    pub tasks: TaskScheduler,
+    pub timers: Timers,
     pub update_cap: UpdateCap,
-    pub renderer: Renderer
+    pub renderer: Renderer,
+    current_window: Option<String>,
+    geometry_file: Option<String>,
+    redraw_requested: bool,
+    fixed_timestep: Option<FixedTimestep>,
+    accumulator: f64,
+    alpha: f64,
+    limiter: FrameLimiter,
+    frame_stats: FrameStats,
+    time: Time,
+    plugins: Vec<Box<dyn Plugin>>,
+    callbacks: Vec<(FramePhase, PhaseCallback)>,
+    resources: HashMap<TypeId, Box<dyn Any>>,
+    batches: Vec<(usize, String)>,
 }
 
 impl App {
@@ -61,24 +119,61 @@ impl App {
     fn new() -> App {
         return App {
             window: None,
+            windows: Vec::new(),
             state_manager: StateManager::new(),
+            tasks: TaskScheduler::new(),
+            timers: Timers::new(),
             update_cap: UpdateCap::Vsync,
             renderer: Renderer::new(),
+            current_window: None,
+            geometry_file: None,
+            redraw_requested: true,
+            fixed_timestep: None,
+            accumulator: 0.0,
+            alpha: 1.0,
+            limiter: FrameLimiter::new(),
+            frame_stats: FrameStats::new(),
+            time: Time::new(),
+            plugins: Vec::new(),
+            callbacks: Vec::new(),
+            resources: HashMap::new(),
+            batches: Vec::new(),
         }
     }
 
     pub fn setup(&mut self, config: AppConfiguration) -> &mut Self {
         self.window = Some(Window::new(config.window_config));
+        // Finished jobs wake up the event loop waiting in OnDemand mode
+        self.tasks.set_loop_waker(post_empty_event);
         self.update_cap = config.update_cap;
+        self.geometry_file = config.geometry_file;
+        self.fixed_timestep = config.fixed_timestep;
+
+        if let Some(geometry) = self.geometry_file.as_ref().and_then(|path| WindowGeometry::load(path)) {
+            self.window().set_geometry(geometry);
+        }
 
         Logger::get().set_level(config.log_level);
 
         self.window.as_mut().unwrap().set_vsync(match self.update_cap {
             UpdateCap::Cap(_) | UpdateCap::Unlimited => false,
-            UpdateCap::Vsync => true,
+            UpdateCap::Vsync | UpdateCap::OnDemand => true,
         });
 
-        self.renderer.init();
+        let target = match self.update_cap {
+            UpdateCap::Cap(cap) => Some(1.0 / cap.max(1) as f64),
+            UpdateCap::Vsync => self.window().get_monitor()
+                .and_then(|monitor| monitor.video_mode)
+                .map(|video_mode| 1.0 / video_mode.refresh_rate.max(1) as f64),
+            UpdateCap::Unlimited | UpdateCap::OnDemand => None,
+        };
+        self.frame_stats.set_target(target);
+
+        let (width, height) = (self.window().get_width(), self.window().get_height());
+        self.renderer.init(width, height);
+        for (id, shader_path) in self.batches.iter() {
+            self.renderer.r2d.register_batch(*id, shader_path);
+        }
 
         self.state_manager.init();
 
@@ -91,54 +186,387 @@ impl App {
             critical!("Onion", "The application hasn't been initialized, call setup() first!");
         }
 
-        let mut last_time = self.window.as_ref().unwrap().get_time();
+        // Don't count the time spent before starting
+        let now = self.window().get_time();
+        self.time.reset(now);
         while !self.window.as_ref().unwrap().should_close() {
-            let now = self.window.as_ref().unwrap().get_time();
-            let delta = now - last_time;
-
-            if match self.update_cap {
-                UpdateCap::Cap(cap) if 1.0 / (cap as f64) <= delta => true,
-                UpdateCap::Vsync | UpdateCap::Unlimited => true,
-                _ => false,
-            } {
-                self.window.as_mut().unwrap().update();
-
-                if self.window.as_mut().unwrap().size_changed() {
-                    self.renderer.resize(self.window.as_mut().unwrap().get_width(), self.window.as_mut().unwrap().get_height());
-
-                    if let Some(mut screen) = get_system().get_attribute_mut::<ScreenProperty>("screen") {
-                        screen.width = self.window().get_width();
-                        screen.height = self.window().get_height();
-                        screen.scale_x = self.window().get_scale_x();
-                        screen.scale_y = self.window().get_scale_y();
-                    } else {
-                        get_system().add_attribute("screen", ScreenProperty {
-                            width: self.window().get_width(),
-                            height: self.window().get_height(),
-                            scale_x: self.window().get_scale_x(),
-                            scale_y: self.window().get_scale_y(),
-                        })
-                    }
+            if let UpdateCap::OnDemand = self.update_cap {
+                if !self.redraw_requested {
+                    // Wake up in time for the next timer
+                    let elapsed = self.window().get_time() - self.time.get_real_time();
+                    let timeout = self.next_timer_in(elapsed).map_or(ON_DEMAND_TIMEOUT, |timeout| timeout.clamp(0.0, ON_DEMAND_TIMEOUT));
+                    self.window().wait_events_timeout(timeout);
                 }
+            }
+
+            self.window.as_mut().unwrap().update();
+            self.windows.iter_mut().for_each(|app_window| app_window.window.update());
+            self.remove_closed_windows();
+            self.dispatch_window_events();
+
+            if let UpdateCap::OnDemand = self.update_cap {
+                let elapsed = self.window().get_time() - self.time.get_real_time();
+                let timer_due = self.next_timer_in(elapsed).is_some_and(|timeout| timeout <= 0.0);
+                if !self.redraw_requested && !self.has_events() && !timer_due && !self.tasks.has_woken_tasks() {
+                    continue;
+                }
+            }
+            self.redraw_requested = false;
+            // Keep rendering until the capture's pixel buffers are read back
+            if self.renderer.is_capturing() {
+                self.redraw_requested = true;
+            }
+
+            self.frame();
+
+            if let UpdateCap::Cap(cap) = self.update_cap {
+                self.limiter.wait(self.window.as_ref().unwrap(), 1.0 / cap.max(1) as f64);
+            }
+        }
+
+        if let Some(path) = &self.geometry_file {
+            self.window.as_ref().unwrap().get_geometry().save(path);
+        }
+
+        self.shutdown();
+    }
+
+    // Everything GL is destroyed while the contexts are still alive
+    fn shutdown(&mut self) {
+        self.state_manager.dispose();
+        self.plugins.iter_mut().for_each(|plugin| plugin.dispose());
+        self.tasks.dispose();
+        self.timers.clear();
+
+        // Secondary windows own their renderer's objects
+        let headless = self.is_headless();
+        for mut app_window in self.windows.drain(..) {
+            app_window.window.make_current();
+            get_gl_registry().set_context(Some(app_window.id.clone()));
+            app_window.renderer.dispose();
+            if !headless {
+                get_gl_registry().dispose_context(&app_window.id);
+            }
+        }
+        self.current_window = None;
+
+        self.window().make_current();
+        get_gl_registry().set_context(None);
+        self.renderer.dispose();
+        if !headless {
+            get_gl_registry().dispose_all();
+        }
+    }
+
+    pub fn add_plugin<P: Plugin>(&mut self, mut plugin: P) -> &mut Self {
+        plugin.build(self);
+        self.plugins.push(Box::new(plugin));
+        self
+    }
+
+    pub fn add_callback<F: FnMut(f64) + 'static>(&mut self, phase: FramePhase, callback: F) {
+        self.callbacks.push((phase, Box::new(callback)));
+    }
+
+    fn run_phase(&mut self, phase: FramePhase, value: f64) {
+        // Hooks may add plugins or callbacks while running
+        let mut index = 0;
+        while index < self.plugins.len() {
+            let mut plugin = MutRef::from(&mut self.plugins[index]);
+            match phase {
+                FramePhase::PreUpdate => plugin.pre_update(value),
+                FramePhase::PostUpdate => plugin.post_update(value),
+                FramePhase::PreRender => plugin.pre_render(value),
+                FramePhase::PostRender => plugin.post_render(value),
+            }
+            index += 1;
+        }
+
+        let mut index = 0;
+        while index < self.callbacks.len() {
+            if self.callbacks[index].0 == phase {
+                let mut callback = MutRef::from(&mut self.callbacks[index].1);
+                callback(value);
+            }
+            index += 1;
+        }
+    }
+
+    // Batches are registered in every renderer on setup, call before setup()
+    pub fn register_batch(&mut self, id: usize, shader_path: &str) {
+        if self.window.is_some() {
+            warn!("Onion", "Batch {} registered after setup, only new windows will render it!", id);
+        }
+        self.batches.push((id, shader_path.to_string()));
+    }
+
+    pub fn add_resource<T: Any>(&mut self, value: T) {
+        self.resources.insert(TypeId::of::<T>(), Box::new(value));
+    }
+
+    pub fn get_resource<T: Any>(&mut self) -> Option<MutRef<T>> {
+        return self.resources.get_mut(&TypeId::of::<T>()).and_then(|resource| resource.downcast_mut::<T>()).map(MutRef::from);
+    }
+
+    pub fn remove_resource<T: Any>(&mut self) -> Option<T> {
+        return self.resources.remove(&TypeId::of::<T>()).and_then(|resource| resource.downcast::<T>().ok()).map(|resource| *resource);
+    }
+
+    // Headless setup, no GLFW window or GL context is created
+    pub fn setup_headless(&mut self, config: AppConfiguration) -> &mut Self {
+        self.window = Some(Window::headless(config.window_config));
+        self.update_cap = config.update_cap;
+        self.fixed_timestep = config.fixed_timestep;
+
+        Logger::get().set_level(config.log_level);
+
+        let (width, height) = (self.window().get_width(), self.window().get_height());
+        self.renderer.init_headless(width, height);
+
+        self.state_manager.init();
+        self
+    }
+
+    // Steps the headless app with a simulated delta, draw calls of the last frame stay recorded
+    pub fn run_frames(&mut self, frames: u32, delta: f64) {
+        if !self.is_headless() {
+            critical!("Onion", "run_frames() requires a headless application, call setup_headless() first!");
+        }
+
+        for _ in 0..frames {
+            if self.window().should_close() {
+                break;
+            }
+
+            self.window().advance_time(delta);
+            self.window.as_mut().unwrap().update();
+            self.windows.iter_mut().for_each(|app_window| app_window.window.update());
+            self.remove_closed_windows();
+            self.dispatch_window_events();
+
+            self.frame();
+        }
+    }
 
-                // Retrieve GL Errors
+    pub fn is_headless(&self) -> bool {
+        return self.window.as_ref().is_some_and(|window| window.is_headless());
+    }
+
+    fn frame(&mut self) {
+        let now = self.window().get_time();
+        self.time.advance(now);
+        self.frame_stats.push(self.time.get_unscaled_delta());
+        // States, timers & the UI run on scaled time
+        let delta = self.time.get_delta();
+
+        if self.window.as_mut().unwrap().size_changed() {
+            self.renderer.resize(self.window.as_mut().unwrap().get_width(), self.window.as_mut().unwrap().get_height());
+        }
+        update_screen(&self.window());
+
+        // Retrieve GL Errors
+        if !self.is_headless() {
+            get_errors();
+        }
+
+        prepare_elements();
+
+        self.renderer.begin_frame();
+        self.renderer.clear();
+        self.tasks.poll();
+        self.timers.update(delta);
+        self.run_phase(FramePhase::PreUpdate, delta);
+        self.fixed_update(delta);
+        self.state_manager.update(delta);
+        self.run_phase(FramePhase::PostUpdate, delta);
+
+        self.run_phase(FramePhase::PreRender, self.alpha);
+        self.state_manager.render(self.alpha);
+
+        // Transitions draw the scene into their targets
+        if !self.state_manager.is_transitioning() {
+            update_scene(delta);
+        }
+
+        self.renderer.render();
+        self.run_phase(FramePhase::PostRender, self.alpha);
+
+        self.update_windows(delta);
+
+        self.renderer.end_frame();
+        self.window.as_mut().unwrap().swap_buffers();
+    }
+
+    fn fixed_update(&mut self, delta: f64) {
+        if let Some(fixed_timestep) = self.fixed_timestep {
+            let step = fixed_timestep.get_delta();
+            self.accumulator += delta;
+
+            let mut steps = 0;
+            while self.accumulator >= step && steps < fixed_timestep.max_steps {
+                self.state_manager.fixed_update(step);
+                self.accumulator -= step;
+                steps += 1;
+            }
+
+            // Too far behind, drop the remaining ticks instead of spiraling
+            if self.accumulator >= step {
+                self.accumulator %= step;
+            }
+
+            self.alpha = self.accumulator / step;
+        }
+    }
+
+    // Real seconds until the next timer fires, None when paused or without timers
+    fn next_timer_in(&self, elapsed: f64) -> Option<f64> {
+        return self.timers.next_due().and_then(|due| self.time.to_real(due)).map(|due| due - elapsed);
+    }
+
+    pub fn get_alpha(&self) -> f64 {
+        return self.alpha;
+    }
+
+    fn update_windows(&mut self, delta: f64) {
+        if self.windows.is_empty() {
+            return;
+        }
+
+        // Windows opened during this loop are updated from the next frame
+        for index in 0..self.windows.len() {
+            let mut app_window = MutRef::from(&mut *self.windows[index]);
+            self.current_window = Some(app_window.id.clone());
+            app_window.window.make_current();
+            get_gl_registry().set_context(Some(app_window.id.clone()));
+
+            if app_window.window.size_changed() {
+                let (width, height) = (app_window.window.get_width(), app_window.window.get_height());
+                app_window.renderer.resize(width, height);
+            }
+            update_screen(&app_window.window);
+
+            // Retrieve GL Errors
+            if !self.is_headless() {
                 get_errors();
+            }
+
+            prepare_elements();
+
+            app_window.renderer.begin_frame();
+            app_window.renderer.clear();
+            let window = MutRef::from(&mut app_window.window);
+            self.state_manager.update_window(app_window.owner, &app_window.id, window, delta);
+
+            update_window_scene(Some(app_window.id.clone()), delta);
+
+            app_window.renderer.render();
+            app_window.renderer.end_frame();
+            app_window.window.swap_buffers();
+        }
 
-                prepare_elements();
+        // Restore the main window
+        self.current_window = None;
+        self.window().make_current();
+        get_gl_registry().set_context(None);
+        update_screen(&self.window());
+    }
+
+    // Route the main window's lifecycle events to the states
+    fn dispatch_window_events(&mut self) {
+        let mut window = self.window();
+        if window.size_changed() {
+            self.state_manager.resize(window.get_width(), window.get_height());
+        }
+        if let Some(focused) = window.focus_changed() {
+            self.state_manager.focus(focused);
+        }
+        if let Some(minimized) = window.minimize_changed() {
+            self.state_manager.minimize(minimized);
+        }
+        if !window.get_dropped_files().is_empty() {
+            self.state_manager.file_drop(window.get_dropped_files());
+        }
+        if window.close_requested() && !self.state_manager.close_requested() {
+            window.cancel_close();
+        }
+    }
+
+    fn has_events(&self) -> bool {
+        return self.window.as_ref().unwrap().has_events() || self.windows.iter().any(|app_window| app_window.window.has_events());
+    }
 
-                self.renderer.clear();
-                self.state_manager.update(delta);
+    pub fn request_redraw(&mut self) {
+        self.redraw_requested = true;
+    }
 
-                update_scene(delta);
+    fn remove_closed_windows(&mut self) {
+        let mut index = 0;
+        while index < self.windows.len() {
+            if self.windows[index].window.should_close() {
+                let mut app_window = self.windows.remove(index);
+
+                // Destroy its objects before its context goes away
+                app_window.window.make_current();
+                get_gl_registry().set_context(Some(app_window.id.clone()));
+                self.state_manager.close_window(app_window.owner, &app_window.id);
+                app_window.renderer.dispose();
+                if !self.is_headless() {
+                    get_gl_registry().dispose_context(&app_window.id);
+                }
+                self.window().make_current();
+                get_gl_registry().set_context(None);
+            } else {
+                index += 1;
+            }
+        }
+    }
 
-                self.renderer.render();
+    pub fn open_window<Owner: IState + 'static>(&mut self, id: &str, config: WindowConfig) -> MutRef<Window> {
+        if self.windows.iter().any(|app_window| app_window.id == id) {
+            critical!("Onion", "Window '{}' already exists!", id);
+        }
 
-                last_time = now;
-                self.window.as_mut().unwrap().swap_buffers();
+        let window = self.window().new_shared(config);
+        let mut app_window = Box::new(AppWindow::new(id.to_string(), TypeId::of::<Owner>(), window));
+
+        // The new window's context is current, setup its renderer
+        get_gl_registry().set_context(Some(id.to_string()));
+        app_window.window.set_vsync(false);
+        let (width, height) = (app_window.window.get_width(), app_window.window.get_height());
+        if self.is_headless() {
+            app_window.renderer.init_headless(width, height);
+        } else {
+            app_window.renderer.init(width, height);
+            for (id, shader_path) in self.batches.iter() {
+                app_window.renderer.r2d.register_batch(*id, shader_path);
             }
         }
+        app_window.window.show();
+        self.windows.push(app_window);
+
+        // Restore the context of the window being rendered
+        if let Some(current) = self.current_window.clone() {
+            self.get_window(&current).unwrap().make_current();
+        } else {
+            self.window().make_current();
+        }
+        get_gl_registry().set_context(self.current_window.clone());
 
-        self.state_manager.dispose();
+        return MutRef::from(&mut self.windows.last_mut().unwrap().window);
+    }
+
+    pub fn get_window(&mut self, id: &str) -> Option<MutRef<Window>> {
+        return self.windows.iter_mut().find(|app_window| app_window.id == id).map(|app_window| MutRef::from(&mut app_window.window));
+    }
+
+    pub fn close_window(&mut self, id: &str) {
+        if let Some(mut window) = self.get_window(id) {
+            window.close();
+        }
+    }
+
+    pub fn current_window(&self) -> Option<String> {
+        return self.current_window.clone();
     }
 
     pub fn window(&mut self) -> MutRef<Window> {
@@ -153,8 +581,49 @@ impl App {
         return MutRef::from(&mut self.state_manager);
     }
 
+    pub fn tasks(&mut self) -> MutRef<TaskScheduler> {
+        return MutRef::from(&mut self.tasks);
+    }
+
+    pub fn timers(&mut self) -> MutRef<Timers> {
+        return MutRef::from(&mut self.timers);
+    }
+
+    pub fn time(&mut self) -> MutRef<Time> {
+        return MutRef::from(&mut self.time);
+    }
+
+    pub fn frame_stats(&mut self) -> MutRef<FrameStats> {
+        return MutRef::from(&mut self.frame_stats);
+    }
+
     pub fn renderer(&mut self) -> MutRef<Renderer> {
+        if let Some(current) = &self.current_window {
+            if let Some(app_window) = self.windows.iter_mut().find(|app_window| app_window.id == *current) {
+                return MutRef::from(&mut app_window.renderer);
+            }
+        }
         return MutRef::from(&mut self.renderer);
     }
 
+}
+
+fn update_screen(window: &Window) {
+    if let Some(mut screen) = get_system().get_attribute_mut::<ScreenProperty>("screen") {
+        screen.width = window.get_width();
+        screen.height = window.get_height();
+        screen.scale_x = window.get_size_ratio_x();
+        screen.scale_y = window.get_size_ratio_y();
+        screen.content_scale_x = window.get_scale_x();
+        screen.content_scale_y = window.get_scale_y();
+    } else {
+        get_system().add_attribute("screen", ScreenProperty {
+            width: window.get_width(),
+            height: window.get_height(),
+            scale_x: window.get_size_ratio_x(),
+            scale_y: window.get_size_ratio_y(),
+            content_scale_x: window.get_scale_x(),
+            content_scale_y: window.get_scale_y(),
+        })
+    }
 }
\ No newline at end of file
@@ -0,0 +1,243 @@
+use cgmath::{vec3, Matrix4, Rad, SquareMatrix, Vector2, Vector4};
+
+use crate::window::input::Mouse;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                            Viewport                                            //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+// Rectangle relative to the render target, from 0.0 to 1.0 with the origin at the top left
+#[derive(Copy, Clone, PartialEq)]
+pub struct Viewport {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Viewport {
+
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> Viewport {
+        return Viewport {
+            x,
+            y,
+            width,
+            height,
+        };
+    }
+
+    pub fn full() -> Viewport {
+        return Viewport::new(0.0, 0.0, 1.0, 1.0);
+    }
+
+    // Pixel rectangle (x, y, width, height) on a target of the given size
+    pub fn to_pixels(&self, width: u32, height: u32) -> (f32, f32, f32, f32) {
+        return (
+            (self.x * width as f32).round(),
+            (self.y * height as f32).round(),
+            (self.width * width as f32).round(),
+            (self.height * height as f32).round(),
+        );
+    }
+
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                            Camera 2D                                           //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+// Looks at `position`, which is drawn at the center of the viewport
+#[derive(Copy, Clone, PartialEq)]
+pub struct Camera2D {
+    position: Vector2<f32>,
+    zoom: f32,
+    // Radians, clockwise on screen
+    rotation: f32,
+    viewport: Viewport,
+}
+
+impl Default for Camera2D {
+
+    fn default() -> Self {
+        return Camera2D::new();
+    }
+
+}
+
+impl Camera2D {
+
+    pub fn new() -> Camera2D {
+        return Camera2D {
+            position: Vector2::new(0.0, 0.0),
+            zoom: 1.0,
+            rotation: 0.0,
+            viewport: Viewport::full(),
+        };
+    }
+
+    pub fn with_viewport(mut self, viewport: Viewport) -> Camera2D {
+        self.viewport = viewport;
+        return self;
+    }
+
+    pub fn with_position(mut self, x: f32, y: f32) -> Camera2D {
+        self.position = Vector2::new(x, y);
+        return self;
+    }
+
+    pub fn with_zoom(mut self, zoom: f32) -> Camera2D {
+        self.set_zoom(zoom);
+        return self;
+    }
+
+    // World to viewport pixels
+    pub fn view_matrix(&self, viewport_width: f32, viewport_height: f32) -> Matrix4<f32> {
+        return Matrix4::from_translation(vec3(viewport_width / 2.0, viewport_height / 2.0, 0.0))
+            * Matrix4::from_angle_z(Rad(self.rotation))
+            * Matrix4::from_scale(self.zoom)
+            * Matrix4::from_translation(vec3(-self.position.x, -self.position.y, 0.0));
+    }
+
+    // Conversions, screen points are in pixels on a target of the given size (the window for mouse positions)
+    pub fn screen_to_world(&self, point: Vector2<f32>, width: u32, height: u32) -> Vector2<f32> {
+        let (x, y, viewport_width, viewport_height) = self.viewport.to_pixels(width, height);
+        let inverse = self.view_matrix(viewport_width, viewport_height).invert().unwrap_or(Matrix4::identity());
+        let world = inverse * Vector4::new(point.x - x, point.y - y, 0.0, 1.0);
+        return Vector2::new(world.x, world.y);
+    }
+
+    pub fn world_to_screen(&self, point: Vector2<f32>, width: u32, height: u32) -> Vector2<f32> {
+        let (x, y, viewport_width, viewport_height) = self.viewport.to_pixels(width, height);
+        let screen = self.view_matrix(viewport_width, viewport_height) * Vector4::new(point.x, point.y, 0.0, 1.0);
+        return Vector2::new(screen.x + x, screen.y + y);
+    }
+
+    pub fn mouse_to_world(&self, mouse: &Mouse, width: u32, height: u32) -> Vector2<f32> {
+        return self.screen_to_world(Vector2::new(mouse.get_x() as f32, mouse.get_y() as f32), width, height);
+    }
+
+    // Whether a screen point is inside the viewport, to route the mouse to the right camera
+    pub fn contains(&self, point: Vector2<f32>, width: u32, height: u32) -> bool {
+        let (x, y, viewport_width, viewport_height) = self.viewport.to_pixels(width, height);
+        return point.x >= x && point.x < x + viewport_width && point.y >= y && point.y < y + viewport_height;
+    }
+
+    // Moves in world units
+    pub fn translate(&mut self, dx: f32, dy: f32) {
+        self.position += Vector2::new(dx, dy);
+    }
+
+    // Zooms while keeping the world point under the screen point in place
+    pub fn zoom_at(&mut self, factor: f32, point: Vector2<f32>, width: u32, height: u32) {
+        let before = self.screen_to_world(point, width, height);
+        self.set_zoom(self.zoom * factor);
+        let after = self.screen_to_world(point, width, height);
+        self.position += before - after;
+    }
+
+    // Getters & Setters
+    pub fn get_position(&self) -> Vector2<f32> {
+        return self.position;
+    }
+
+    pub fn set_position(&mut self, x: f32, y: f32) {
+        self.position = Vector2::new(x, y);
+    }
+
+    pub fn get_zoom(&self) -> f32 {
+        return self.zoom;
+    }
+
+    pub fn set_zoom(&mut self, zoom: f32) {
+        self.zoom = zoom.max(f32::EPSILON);
+    }
+
+    pub fn get_rotation(&self) -> f32 {
+        return self.rotation;
+    }
+
+    pub fn set_rotation(&mut self, rotation: f32) {
+        self.rotation = rotation;
+    }
+
+    pub fn get_viewport(&self) -> Viewport {
+        return self.viewport;
+    }
+
+    pub fn set_viewport(&mut self, viewport: Viewport) {
+        self.viewport = viewport;
+    }
+
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                              Tests                                             //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use super::*;
+
+    fn assert_near(a: Vector2<f32>, b: Vector2<f32>) {
+        assert!((a.x - b.x).abs() < 1e-3 && (a.y - b.y).abs() < 1e-3, "({}, {}) != ({}, {})", a.x, a.y, b.x, b.y);
+    }
+
+    #[test]
+    fn centered_on_position() {
+        let camera = Camera2D::default().with_position(100.0, 50.0).with_zoom(2.0);
+        assert_near(camera.world_to_screen(Vector2::new(100.0, 50.0), 800, 600), Vector2::new(400.0, 300.0));
+        assert_near(camera.world_to_screen(Vector2::new(110.0, 50.0), 800, 600), Vector2::new(420.0, 300.0));
+        assert_near(camera.screen_to_world(Vector2::new(0.0, 0.0), 800, 600), Vector2::new(-100.0, -100.0));
+    }
+
+    #[test]
+    fn round_trips() {
+        let mut camera = Camera2D::new()
+            .with_position(-32.0, 12.5)
+            .with_zoom(0.75)
+            .with_viewport(Viewport::new(0.5, 0.25, 0.5, 0.5));
+        camera.set_rotation(0.6);
+
+        for point in [Vector2::new(0.0, 0.0), Vector2::new(512.0, 200.0), Vector2::new(-40.0, 999.0)] {
+            let screen = camera.world_to_screen(point, 1024, 768);
+            assert_near(camera.screen_to_world(screen, 1024, 768), point);
+            let world = camera.screen_to_world(point, 1024, 768);
+            assert_near(camera.world_to_screen(world, 1024, 768), point);
+        }
+    }
+
+    #[test]
+    fn rotation_is_clockwise() {
+        let mut camera = Camera2D::new();
+        camera.set_rotation(FRAC_PI_2);
+        // With y pointing down, +x in the world ends up below the center
+        assert_near(camera.world_to_screen(Vector2::new(10.0, 0.0), 100, 100), Vector2::new(50.0, 60.0));
+    }
+
+    #[test]
+    fn viewports() {
+        let camera = Camera2D::new().with_viewport(Viewport::new(0.5, 0.0, 0.5, 1.0));
+        assert_eq!(camera.get_viewport().to_pixels(800, 600), (400.0, 0.0, 400.0, 600.0));
+        assert!(camera.contains(Vector2::new(400.0, 0.0), 800, 600));
+        assert!(!camera.contains(Vector2::new(399.0, 300.0), 800, 600));
+        assert!(!camera.contains(Vector2::new(800.0, 300.0), 800, 600));
+        // The world origin is at the center of the viewport
+        assert_near(camera.world_to_screen(Vector2::new(0.0, 0.0), 800, 600), Vector2::new(600.0, 300.0));
+    }
+
+    #[test]
+    fn zoom_at_keeps_point() {
+        let mut camera = Camera2D::new().with_position(20.0, -5.0);
+        let point = Vector2::new(700.0, 100.0);
+        let before = camera.screen_to_world(point, 800, 600);
+        camera.zoom_at(3.0, point, 800, 600);
+        assert_eq!(camera.get_zoom(), 3.0);
+        assert_near(camera.screen_to_world(point, 800, 600), before);
+
+        camera.set_zoom(-1.0);
+        assert!(camera.get_zoom() > 0.0);
+    }
+
+}
\ No newline at end of file
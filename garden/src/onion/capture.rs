@@ -0,0 +1,236 @@
+use std::fs;
+use std::io;
+use std::ptr;
+
+use gl::types::{GLsizeiptr, GLsync, GLuint};
+
+use crate::{error, info, garlic::{get_gl_registry, GLResource, Image}};
+
+use super::App;
+
+// Blocking wait used when the renderer is disposed with captures in flight, in nanoseconds
+const DISPOSE_TIMEOUT: u64 = 1_000_000_000;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                          Frame Capture                                         //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+// Pixels copied into a PBO, mapped once the GPU is done with the copy
+struct PendingCapture {
+    pbo: GLuint,
+    fence: GLsync,
+    width: u32,
+    height: u32,
+    path: String,
+}
+
+struct CaptureSequence {
+    directory: String,
+    frame: u32,
+    remaining: Option<u32>,
+}
+
+pub struct FrameCapture {
+    requests: Vec<String>,
+    pending: Vec<PendingCapture>,
+    sequence: Option<CaptureSequence>,
+}
+
+impl FrameCapture {
+
+    pub fn new() -> FrameCapture {
+        return FrameCapture {
+            requests: Vec::new(),
+            pending: Vec::new(),
+            sequence: None,
+        };
+    }
+
+    pub fn request(&mut self, path: &str) {
+        self.requests.push(path.to_string());
+    }
+
+    pub fn start_sequence(&mut self, directory: &str, frames: Option<u32>) {
+        // Nothing to record
+        if frames == Some(0) {
+            self.sequence = None;
+            return;
+        }
+
+        if let Err(err) = fs::create_dir_all(directory) {
+            error!("Onion", "Failed to create capture directory '{}', {}", directory, err);
+            return;
+        }
+
+        self.sequence = Some(CaptureSequence {
+            directory: directory.to_string(),
+            frame: 0,
+            remaining: frames,
+        });
+    }
+
+    pub fn stop_sequence(&mut self) {
+        self.sequence = None;
+    }
+
+    pub fn is_capturing_sequence(&self) -> bool {
+        return self.sequence.is_some();
+    }
+
+    // Requested, in flight or recording frames
+    pub fn is_busy(&self) -> bool {
+        return !self.requests.is_empty() || !self.pending.is_empty() || self.sequence.is_some();
+    }
+
+    // Called after the frame is rendered, before the buffers are swapped
+    pub fn end_frame(&mut self, width: u32, height: u32) {
+        self.collect(false);
+
+        let mut paths: Vec<String> = self.requests.drain(..).collect();
+        if let Some(sequence) = &mut self.sequence {
+            paths.push(format!("{}/frame_{:05}.png", sequence.directory, sequence.frame));
+            sequence.frame += 1;
+            if let Some(remaining) = &mut sequence.remaining {
+                *remaining -= 1;
+                if *remaining == 0 {
+                    self.sequence = None;
+                }
+            }
+        }
+
+        for path in paths {
+            self.read_async(width, height, path);
+        }
+    }
+
+    fn read_async(&mut self, width: u32, height: u32, path: String) {
+        let mut pbo = 0;
+        let fence;
+        unsafe {
+            gl::GenBuffers(1, &mut pbo);
+            gl::BindBuffer(gl::PIXEL_PACK_BUFFER, pbo);
+            gl::BufferData(gl::PIXEL_PACK_BUFFER, (width as usize * height as usize * 4) as GLsizeiptr, ptr::null(), gl::STREAM_READ);
+
+            gl::BindFramebuffer(gl::READ_FRAMEBUFFER, 0);
+            gl::ReadBuffer(gl::BACK);
+            gl::PixelStorei(gl::PACK_ALIGNMENT, 1);
+            // Returns immediately, the copy happens on the GPU
+            gl::ReadPixels(0, 0, width as i32, height as i32, gl::RGBA, gl::UNSIGNED_BYTE, ptr::null_mut());
+            fence = gl::FenceSync(gl::SYNC_GPU_COMMANDS_COMPLETE, 0);
+
+            gl::BindBuffer(gl::PIXEL_PACK_BUFFER, 0);
+        }
+        get_gl_registry().track(GLResource::Buffer(pbo), "FrameCapture");
+
+        self.pending.push(PendingCapture {
+            pbo,
+            fence,
+            width,
+            height,
+            path,
+        });
+    }
+
+    // Saves the captures the GPU is done with, wait blocks until every capture is done
+    fn collect(&mut self, wait: bool) {
+        let mut index = 0;
+        while index < self.pending.len() {
+            let capture = &self.pending[index];
+            let status = unsafe {
+                if wait {
+                    gl::ClientWaitSync(capture.fence, gl::SYNC_FLUSH_COMMANDS_BIT, DISPOSE_TIMEOUT)
+                } else {
+                    gl::ClientWaitSync(capture.fence, 0, 0)
+                }
+            };
+            if status != gl::ALREADY_SIGNALED && status != gl::CONDITION_SATISFIED && !wait {
+                index += 1;
+                continue;
+            }
+
+            let capture = self.pending.remove(index);
+            let image = if status == gl::ALREADY_SIGNALED || status == gl::CONDITION_SATISFIED {
+                Some(map_capture(&capture))
+            } else {
+                error!("Onion", "Capture '{}' timed out!", capture.path);
+                None
+            };
+            delete_capture(&capture);
+
+            if let Some(image) = image {
+                save_capture(image, capture.path, wait);
+            }
+        }
+    }
+
+    // The renderer's context must be current
+    pub fn dispose(&mut self) {
+        self.requests.clear();
+        self.sequence = None;
+        self.collect(true);
+    }
+
+}
+
+fn map_capture(capture: &PendingCapture) -> Image {
+    let size = capture.width as usize * capture.height as usize * 4;
+    let mut pixels = vec![0u8; size];
+    unsafe {
+        gl::BindBuffer(gl::PIXEL_PACK_BUFFER, capture.pbo);
+        let data = gl::MapBufferRange(gl::PIXEL_PACK_BUFFER, 0, size as GLsizeiptr, gl::MAP_READ_BIT) as *const u8;
+        if data.is_null() {
+            error!("Onion", "Failed to map capture '{}'!", capture.path);
+        } else {
+            ptr::copy_nonoverlapping(data, pixels.as_mut_ptr(), size);
+            gl::UnmapBuffer(gl::PIXEL_PACK_BUFFER);
+        }
+        gl::BindBuffer(gl::PIXEL_PACK_BUFFER, 0);
+    }
+    return Image::from_pixels(capture.width, capture.height, pixels);
+}
+
+fn delete_capture(capture: &PendingCapture) {
+    unsafe {
+        gl::DeleteSync(capture.fence);
+        gl::DeleteBuffers(1, &capture.pbo);
+    }
+    get_gl_registry().release(GLResource::Buffer(capture.pbo));
+}
+
+// Encoding runs on the job pool, unless the app is shutting down
+fn save_capture(mut image: Image, path: String, blocking: bool) {
+    let save = move || {
+        image.flip_vertically();
+        let result = image.save_png(&path);
+        return (path, result);
+    };
+
+    // The logger isn't thread safe, the result is logged on the main thread
+    if blocking {
+        log_capture(save());
+    } else {
+        App::get().tasks().spawn_job_then(save, log_capture);
+    }
+}
+
+fn log_capture((path, result): (String, io::Result<()>)) {
+    match result {
+        Ok(_) => info!("Onion", "Frame saved to '{}'", path),
+        Err(err) => error!("Onion", "Failed to save frame to '{}', {}", path, err),
+    }
+}
+
+// Blocking read of the back buffer, rows top to bottom
+pub fn read_frame(width: u32, height: u32) -> Image {
+    let mut pixels = vec![0u8; width as usize * height as usize * 4];
+    unsafe {
+        gl::BindFramebuffer(gl::READ_FRAMEBUFFER, 0);
+        gl::ReadBuffer(gl::BACK);
+        gl::PixelStorei(gl::PACK_ALIGNMENT, 1);
+        gl::ReadPixels(0, 0, width as i32, height as i32, gl::RGBA, gl::UNSIGNED_BYTE, pixels.as_mut_ptr() as *mut _);
+    }
+
+    let mut image = Image::from_pixels(width, height, pixels);
+    image.flip_vertically();
+    return image;
+}
\ No newline at end of file
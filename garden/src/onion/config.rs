@@ -0,0 +1,247 @@
+use std::{env, fs};
+
+use toml::{Table, Value};
+
+use crate::{potato::LogLevel, warn, debug};
+
+use super::{AppConfiguration, FixedTimestep, UpdateCap};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                          Config Loading                                        //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+// Prefix of the environment variables, GARDEN_LOG_LEVEL mirrors --log-level
+const ENV_PREFIX: &str = "GARDEN_";
+
+// Command line flags & their key in the config file
+const FLAGS: [(&str, &str); 10] = [
+    ("log-level", "log_level"),
+    ("update-cap", "update_cap"),
+    ("fixed-timestep", "fixed_timestep"),
+    ("geometry-file", "geometry_file"),
+    ("size", "window.size"),
+    ("width", "window.width"),
+    ("height", "window.height"),
+    ("title", "window.title"),
+    ("monitor", "window.monitor"),
+    ("fullscreen", "window.fullscreen"),
+];
+
+impl AppConfiguration {
+
+    // Defaults, overlaid with the config file (if any), the environment & the command line
+    pub fn load(path: &str) -> AppConfiguration {
+        let mut config = AppConfiguration::default();
+        config.load_file(path);
+        config.apply_env();
+        config.apply_args(env::args().skip(1));
+        return config;
+    }
+
+    // TOML file, top-level keys & a [window] table
+    pub fn load_file(&mut self, path: &str) {
+        let src = match fs::read_to_string(path) {
+            Ok(src) => src,
+            Err(_) => {
+                debug!("Onion", "No config file at '{}', using defaults", path);
+                return;
+            }
+        };
+        self.load_src(&src, path);
+    }
+
+    fn load_src(&mut self, src: &str, source: &str) {
+        match src.parse::<Table>() {
+            Ok(table) => self.load_table(&table, "", source),
+            Err(err) => warn!("Onion", "Invalid config file '{}', {}", source, err),
+        }
+    }
+
+    fn load_table(&mut self, table: &Table, prefix: &str, source: &str) {
+        for (key, value) in table {
+            let key = format!("{}{}", prefix, key);
+            match value {
+                Value::String(value) => self.set(&key, value, source),
+                Value::Integer(_) | Value::Float(_) | Value::Boolean(_) => self.set(&key, &value.to_string(), source),
+                // Only one level of tables, e.g. [window]
+                Value::Table(table) if prefix.is_empty() => self.load_table(table, &format!("{}.", key), source),
+                _ => warn!("Onion", "Unsupported value for '{}' in {}!", key, source),
+            }
+        }
+    }
+
+    pub fn apply_env(&mut self) {
+        for (flag, key) in FLAGS {
+            let name = format!("{}{}", ENV_PREFIX, flag.to_uppercase().replace('-', "_"));
+            if let Ok(value) = env::var(&name) {
+                self.set(key, &value, &name);
+            }
+        }
+    }
+
+    // Accepts `--flag value`, `--flag=value` & `--fullscreen [true|false]`, other arguments are left to the app
+    pub fn apply_args<I: IntoIterator<Item = String>>(&mut self, args: I) {
+        let mut args = args.into_iter().peekable();
+        while let Some(arg) = args.next() {
+            let flag = match arg.strip_prefix("--") {
+                Some(flag) => flag,
+                None => continue,
+            };
+            let (flag, inline) = match flag.split_once('=') {
+                Some((flag, value)) => (flag, Some(value.to_string())),
+                None => (flag, None),
+            };
+            let key = match FLAGS.iter().find(|(name, _)| *name == flag) {
+                Some((_, key)) => *key,
+                None => continue,
+            };
+
+            let value = if let Some(value) = inline {
+                value
+            } else if key == "window.fullscreen" {
+                args.next_if(|value| value.parse::<bool>().is_ok()).unwrap_or("true".to_string())
+            } else if let Some(value) = args.next_if(|value| !value.starts_with("--")) {
+                value
+            } else {
+                warn!("Onion", "Missing value for '--{}'!", flag);
+                continue;
+            };
+            self.set(key, &value, "command line");
+        }
+    }
+
+    fn set(&mut self, key: &str, value: &str, source: &str) {
+        let parsed = match key {
+            "log_level" => parse_log_level(value).map(|level| self.log_level = level).is_some(),
+            "update_cap" => parse_update_cap(value).map(|cap| self.update_cap = cap).is_some(),
+            "fixed_timestep" => value.parse().map(|rate| self.fixed_timestep = Some(FixedTimestep::new(rate))).is_ok(),
+            "geometry_file" => {
+                self.geometry_file = Some(value.to_string());
+                true
+            }
+            "window.size" => value.split_once('x')
+                .and_then(|(width, height)| Some((width.trim().parse().ok()?, height.trim().parse().ok()?)))
+                .map(|(width, height)| {
+                    self.window_config.width = width;
+                    self.window_config.height = height;
+                }).is_some(),
+            "window.width" => value.parse().map(|width| self.window_config.width = width).is_ok(),
+            "window.height" => value.parse().map(|height| self.window_config.height = height).is_ok(),
+            "window.title" => {
+                self.window_config.title = value.to_string();
+                true
+            }
+            "window.monitor" => value.parse().map(|monitor| self.window_config.monitor = Some(monitor)).is_ok(),
+            "window.fullscreen" => value.parse().map(|fullscreen| self.window_config.fullscreen = fullscreen).is_ok(),
+            _ => {
+                warn!("Onion", "Unknown config key '{}' in {}!", key, source);
+                true
+            }
+        };
+        if !parsed {
+            warn!("Onion", "Invalid value '{}' for '{}' in {}!", value, key, source);
+        }
+    }
+
+}
+
+fn parse_log_level(value: &str) -> Option<LogLevel> {
+    return match value.to_lowercase().as_str() {
+        "critical" => Some(LogLevel::Critical),
+        "error" => Some(LogLevel::Error),
+        "warning" | "warn" => Some(LogLevel::Warning),
+        "info" => Some(LogLevel::Info),
+        "debug" => Some(LogLevel::Debug),
+        _ => None,
+    }
+}
+
+// "vsync", "unlimited", "on_demand" or a frame rate
+fn parse_update_cap(value: &str) -> Option<UpdateCap> {
+    return match value.to_lowercase().as_str() {
+        "vsync" => Some(UpdateCap::Vsync),
+        "unlimited" => Some(UpdateCap::Unlimited),
+        "on_demand" | "on-demand" => Some(UpdateCap::OnDemand),
+        cap => cap.parse().ok().map(UpdateCap::Cap),
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                              Tests                                             //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        return args.iter().map(|arg| arg.to_string()).collect();
+    }
+
+    #[test]
+    fn load_toml() {
+        let mut config = AppConfiguration::default();
+        config.load_src(r#"
+            # Comment
+            log_level = "debug"
+            update_cap = 144
+            fixed_timestep = 60
+            geometry_file = 'C:\garden\geometry.txt'
+
+            [window]
+            title = "Say \"hi\" # not a comment"
+            size = "1280x720"
+            fullscreen = true
+        "#, "test");
+
+        assert!(config.log_level == LogLevel::Debug);
+        assert!(matches!(config.update_cap, UpdateCap::Cap(144)));
+        assert_eq!(config.fixed_timestep.map(|timestep| timestep.rate), Some(60));
+        assert_eq!(config.geometry_file.as_deref(), Some("C:\\garden\\geometry.txt"));
+        assert_eq!(config.window_config.title, "Say \"hi\" # not a comment");
+        assert_eq!((config.window_config.width, config.window_config.height), (1280, 720));
+        assert!(config.window_config.fullscreen);
+    }
+
+    #[test]
+    fn reject_unsupported_values() {
+        let mut config = AppConfiguration::default();
+        config.load_src(r#"
+            title = ["a", "b"]
+            fixed_timestep = 60.5
+            [window]
+            width = { value = 10 }
+            height = -1
+        "#, "test");
+        assert!(config.fixed_timestep.is_none());
+        assert_eq!((config.window_config.width, config.window_config.height), (800, 600));
+
+        // Invalid files are ignored entirely
+        config.load_src("log_level = \"debug\"\nwidth = ", "test");
+        assert!(config.log_level == LogLevel::Info);
+    }
+
+    #[test]
+    fn apply_command_line() {
+        let mut config = AppConfiguration::default();
+        config.apply_args(args(&["game.save", "--log-level", "warn", "--size=640x480", "--title", "Garden", "--unknown", "--fullscreen"]));
+        assert!(config.log_level == LogLevel::Warning);
+        assert_eq!((config.window_config.width, config.window_config.height), (640, 480));
+        assert_eq!(config.window_config.title, "Garden");
+        assert!(config.window_config.fullscreen);
+
+        // Fullscreen takes an optional boolean
+        config.apply_args(args(&["--fullscreen", "false", "--monitor", "1"]));
+        assert!(!config.window_config.fullscreen);
+        assert_eq!(config.window_config.monitor, Some(1));
+        config.apply_args(args(&["--fullscreen", "save.txt"]));
+        assert!(config.window_config.fullscreen);
+        config.apply_args(args(&["--fullscreen=false"]));
+        assert!(!config.window_config.fullscreen);
+
+        // A flag followed by another flag has no value
+        config.apply_args(args(&["--width", "--height", "300"]));
+        assert_eq!((config.window_config.width, config.window_config.height), (640, 300));
+    }
+
+}
\ No newline at end of file
@@ -2,5 +2,25 @@ mod state;
 pub use state::*;
 mod renderer;
 pub use renderer::*;
+mod transition;
+pub use transition::*;
+mod post;
+pub use post::*;
+mod camera;
+pub use camera::*;
+mod window;
+pub use window::*;
+mod pacing;
+pub use pacing::*;
+mod plugin;
+pub use plugin::*;
+mod tasks;
+pub use tasks::*;
+mod timer;
+pub use timer::*;
+mod time;
+pub use time::*;
 mod app;
-pub use app::*;
\ No newline at end of file
+pub use app::*;
+mod config;
+mod capture;
\ No newline at end of file
@@ -0,0 +1,242 @@
+use std::{thread, time::Duration};
+
+use crate::tomato::Window;
+
+// Time (in seconds) left to busy-wait after sleeping, sleep isn't precise enough for the rest
+const SPIN_MARGIN: f64 = 0.002;
+// Number of frames kept for the statistics
+const STATS_CAPACITY: usize = 240;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                          Frame Limiter                                         //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct FrameLimiter {
+    deadline: Option<f64>,
+}
+
+impl Default for FrameLimiter {
+
+    fn default() -> Self {
+        return FrameLimiter::new();
+    }
+
+}
+
+impl FrameLimiter {
+
+    pub fn new() -> FrameLimiter {
+        return FrameLimiter {
+            deadline: None
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.deadline = None;
+    }
+
+    // Waits until the next frame deadline, returns false if the deadline was already missed
+    pub fn wait(&mut self, window: &Window, frame_time: f64) -> bool {
+        let now = window.get_time();
+        let deadline = self.deadline.map_or(now + frame_time, |deadline| deadline + frame_time);
+
+        // Too late, start again from now instead of rushing the next frames
+        if now > deadline {
+            self.deadline = Some(now);
+            return false;
+        }
+
+        // Sleep for most of the remaining time, then spin until the deadline
+        let remaining = deadline - now - SPIN_MARGIN;
+        if remaining > 0.0 {
+            thread::sleep(Duration::from_secs_f64(remaining));
+        }
+        while window.get_time() < deadline {
+            std::hint::spin_loop();
+        }
+
+        self.deadline = Some(deadline);
+        return true;
+    }
+
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                           Frame Stats                                          //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct FrameStats {
+    frame_times: Vec<f64>,
+    next: usize,
+    // Expected frame time, frames taking more than 1.5x are dropped
+    target: Option<f64>,
+    frames: u64,
+    dropped: u64,
+}
+
+impl Default for FrameStats {
+
+    fn default() -> Self {
+        return FrameStats::new();
+    }
+
+}
+
+impl FrameStats {
+
+    pub fn new() -> FrameStats {
+        return FrameStats {
+            frame_times: Vec::with_capacity(STATS_CAPACITY),
+            next: 0,
+            target: None,
+            frames: 0,
+            dropped: 0,
+        }
+    }
+
+    pub fn push(&mut self, frame_time: f64) {
+        if self.frame_times.len() < STATS_CAPACITY {
+            self.frame_times.push(frame_time);
+        } else {
+            self.frame_times[self.next] = frame_time;
+        }
+        self.next = (self.next + 1) % STATS_CAPACITY;
+
+        self.frames += 1;
+        if let Some(target) = self.target {
+            if frame_time > target * 1.5 {
+                self.dropped += 1;
+            }
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.frame_times.clear();
+        self.next = 0;
+        self.frames = 0;
+        self.dropped = 0;
+    }
+
+    pub fn set_target(&mut self, target: Option<f64>) {
+        self.target = target;
+    }
+
+    pub fn get_target(&self) -> Option<f64> {
+        return self.target;
+    }
+
+    // Getters, computed over the last frames
+    pub fn get_min(&self) -> f64 {
+        return self.frame_times.iter().cloned().fold(f64::INFINITY, f64::min).min(self.get_max());
+    }
+
+    pub fn get_max(&self) -> f64 {
+        return self.frame_times.iter().cloned().fold(0.0, f64::max);
+    }
+
+    pub fn get_average(&self) -> f64 {
+        if self.frame_times.is_empty() {
+            return 0.0;
+        }
+        return self.frame_times.iter().sum::<f64>() / self.frame_times.len() as f64;
+    }
+
+    // Percentile between 0 and 100, e.g. 99 for the 99th percentile frame time
+    pub fn get_percentile(&self, percentile: f64) -> f64 {
+        if self.frame_times.is_empty() {
+            return 0.0;
+        }
+
+        let mut sorted = self.frame_times.clone();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let index = ((percentile.clamp(0.0, 100.0) / 100.0) * (sorted.len() - 1) as f64).round() as usize;
+        return sorted[index];
+    }
+
+    pub fn get_fps(&self) -> f64 {
+        let average = self.get_average();
+        return if average > 0.0 { 1.0 / average } else { 0.0 };
+    }
+
+    // Totals since the start
+    pub fn get_frame_count(&self) -> u64 {
+        return self.frames;
+    }
+
+    pub fn get_dropped(&self) -> u64 {
+        return self.dropped;
+    }
+
+}
+
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                              Tests                                             //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_stats() {
+        let stats = FrameStats::default();
+        assert_eq!(stats.get_average(), 0.0);
+        assert_eq!(stats.get_percentile(99.0), 0.0);
+        assert_eq!(stats.get_fps(), 0.0);
+    }
+
+    #[test]
+    fn percentiles() {
+        let mut stats = FrameStats::new();
+        for i in 1..=100 {
+            stats.push(i as f64 / 1000.0);
+        }
+        assert_eq!(stats.get_min(), 0.001);
+        assert_eq!(stats.get_max(), 0.1);
+        assert_eq!(stats.get_percentile(0.0), 0.001);
+        assert_eq!(stats.get_percentile(50.0), 0.051);
+        assert_eq!(stats.get_percentile(99.0), 0.099);
+        assert_eq!(stats.get_percentile(100.0), 0.1);
+        // Out of range percentiles are clamped
+        assert_eq!(stats.get_percentile(150.0), 0.1);
+        assert!((stats.get_average() - 0.0505).abs() < 1e-9);
+    }
+
+    #[test]
+    fn keeps_last_frames() {
+        let mut stats = FrameStats::new();
+        for _ in 0..STATS_CAPACITY {
+            stats.push(1.0);
+        }
+        for _ in 0..STATS_CAPACITY {
+            stats.push(0.5);
+        }
+        assert_eq!(stats.get_max(), 0.5);
+        assert_eq!(stats.get_fps(), 2.0);
+        assert_eq!(stats.get_frame_count(), 2 * STATS_CAPACITY as u64);
+    }
+
+    #[test]
+    fn counts_dropped_frames() {
+        let mut stats = FrameStats::new();
+
+        // Without a target nothing is dropped
+        stats.push(1.0);
+        assert_eq!(stats.get_dropped(), 0);
+
+        stats.set_target(Some(1.0 / 60.0));
+        stats.push(1.0 / 60.0);
+        stats.push(1.4 / 60.0);
+        stats.push(1.6 / 60.0);
+        stats.push(3.0 / 60.0);
+        assert_eq!(stats.get_dropped(), 2);
+        assert_eq!(stats.get_frame_count(), 5);
+
+        // Reset clears the totals but keeps the target
+        stats.reset();
+        assert_eq!((stats.get_frame_count(), stats.get_dropped()), (0, 0));
+        assert_eq!(stats.get_target(), Some(1.0 / 60.0));
+    }
+
+}
\ No newline at end of file
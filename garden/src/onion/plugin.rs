@@ -0,0 +1,38 @@
+use std::any::Any;
+
+use super::App;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                           Frame Phase                                          //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum FramePhase {
+    // Before the fixed & state updates
+    PreUpdate,
+    // After the state updates
+    PostUpdate,
+    // Before the states and the scene are rendered
+    PreRender,
+    // After the frame has been flushed, before swapping buffers
+    PostRender
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                             Plugin                                             //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub trait Plugin: Any {
+
+    // Called once when added, register states, batches, resources & callbacks here
+    fn build(&mut self, app: &mut App);
+
+    // Per-frame hooks, update phases receive the frame delta, render phases the interpolation alpha
+    fn pre_update(&mut self, _delta: f64) {}
+    fn post_update(&mut self, _delta: f64) {}
+    fn pre_render(&mut self, _alpha: f64) {}
+    fn post_render(&mut self, _alpha: f64) {}
+
+    fn dispose(&mut self) {}
+
+}
\ No newline at end of file
@@ -0,0 +1,170 @@
+use cgmath::{Vector2, Vector3};
+
+use crate::garlic::{Color, Shader};
+
+// Shared by every post pass & the transitions, outputs fUV over a fullscreen quad
+pub const FULLSCREEN_VERTEX_SHADER: &str = "res/shaders/fullscreen/VS.glsl";
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                          Post Uniform                                          //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Clone)]
+pub enum PostUniform {
+    I32(i32),
+    F32(f32),
+    Vec2(Vector2<f32>),
+    Vec3(Vector3<f32>),
+    Color(Color),
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                           Post Effect                                          //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+// A fullscreen pass reading the previous image from `uScene`, `uTexelSize` is loaded when declared
+pub struct PostEffect {
+    name: String,
+    shader: Shader,
+    uniforms: Vec<(String, PostUniform)>,
+    // Separable effects run twice, horizontally then vertically through `uDirection`
+    separable: bool,
+    enabled: bool,
+}
+
+impl PostEffect {
+
+    pub fn new<Str: ToString>(name: Str, shader: Shader) -> PostEffect {
+        return PostEffect {
+            name: name.to_string(),
+            shader,
+            uniforms: Vec::new(),
+            separable: false,
+            enabled: true,
+        };
+    }
+
+    // User pass from a fragment shader, paired with the shared post vertex shader
+    pub fn custom<Str: ToString>(name: Str, fragment: Str) -> PostEffect {
+        return PostEffect::new(name, Shader::load_files(FULLSCREEN_VERTEX_SHADER.to_string(), fragment.to_string()));
+    }
+
+    pub fn blur(radius: f32) -> PostEffect {
+        let mut effect = PostEffect::custom("blur", "res/shaders/post/blur.glsl");
+        effect.separable = true;
+        effect.set_f32("uRadius", radius);
+        return effect;
+    }
+
+    // Brightness is added, contrast & saturation are factors around 1.0
+    pub fn color_grading(brightness: f32, contrast: f32, saturation: f32, tint: Color) -> PostEffect {
+        let mut effect = PostEffect::custom("color_grading", "res/shaders/post/color_grading.glsl");
+        effect.set_f32("uBrightness", brightness);
+        effect.set_f32("uContrast", contrast);
+        effect.set_f32("uSaturation", saturation);
+        effect.set_color("uTint", tint);
+        return effect;
+    }
+
+    // Radius & softness are relative to the screen's half diagonal
+    pub fn vignette(intensity: f32, radius: f32, softness: f32) -> PostEffect {
+        let mut effect = PostEffect::custom("vignette", "res/shaders/post/vignette.glsl");
+        effect.set_f32("uIntensity", intensity);
+        effect.set_f32("uRadius", radius);
+        effect.set_f32("uSoftness", softness);
+        return effect;
+    }
+
+    pub fn fxaa() -> PostEffect {
+        return PostEffect::custom("fxaa", "res/shaders/post/fxaa.glsl");
+    }
+
+    pub fn with_enabled(mut self, enabled: bool) -> PostEffect {
+        self.enabled = enabled;
+        return self;
+    }
+
+    // Uniforms
+    pub fn set_uniform<Str: ToString>(&mut self, name: Str, value: PostUniform) {
+        let name = name.to_string();
+        if let Some((_, uniform)) = self.uniforms.iter_mut().find(|(uniform, _)| *uniform == name) {
+            *uniform = value;
+        } else {
+            self.uniforms.push((name, value));
+        }
+    }
+
+    pub fn set_i32<Str: ToString>(&mut self, name: Str, value: i32) {
+        self.set_uniform(name, PostUniform::I32(value));
+    }
+
+    pub fn set_f32<Str: ToString>(&mut self, name: Str, value: f32) {
+        self.set_uniform(name, PostUniform::F32(value));
+    }
+
+    pub fn set_vec2<Str: ToString>(&mut self, name: Str, value: Vector2<f32>) {
+        self.set_uniform(name, PostUniform::Vec2(value));
+    }
+
+    pub fn set_vec3<Str: ToString>(&mut self, name: Str, value: Vector3<f32>) {
+        self.set_uniform(name, PostUniform::Vec3(value));
+    }
+
+    pub fn set_color<Str: ToString>(&mut self, name: Str, value: Color) {
+        self.set_uniform(name, PostUniform::Color(value));
+    }
+
+    pub fn get_uniform(&self, name: &str) -> Option<&PostUniform> {
+        return self.uniforms.iter().find(|(uniform, _)| uniform == name).map(|(_, value)| value);
+    }
+
+    // Binds the shader & loads every uniform, the scene texture is expected on unit 0
+    pub(crate) fn prepare(&mut self, width: u32, height: u32) {
+        self.shader.bind();
+        self.shader.load_i32("uScene", 0);
+        if self.shader.has_uniform("uTexelSize") {
+            self.shader.load_vec2_f32("uTexelSize", Vector2::new(1.0 / width as f32, 1.0 / height as f32));
+        }
+        for (name, value) in self.uniforms.iter() {
+            match value.clone() {
+                PostUniform::I32(value) => self.shader.load_i32(name, value),
+                PostUniform::F32(value) => self.shader.load_f32(name, value),
+                PostUniform::Vec2(value) => self.shader.load_vec2_f32(name, value),
+                PostUniform::Vec3(value) => self.shader.load_vec3_f32(name, value),
+                PostUniform::Color(value) => self.shader.load_color(name, value),
+            }
+        }
+    }
+
+    pub(crate) fn set_pass(&mut self, pass: usize) {
+        if self.separable {
+            self.shader.load_vec2_f32("uDirection", if pass == 0 { Vector2::new(1.0, 0.0) } else { Vector2::new(0.0, 1.0) });
+        }
+    }
+
+    pub(crate) fn dispose(&self) {
+        self.shader.dispose();
+    }
+
+    // Getters & Setters
+    pub fn get_name(&self) -> &str {
+        return &self.name;
+    }
+
+    pub fn get_passes(&self) -> usize {
+        return if self.separable { 2 } else { 1 };
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        return self.enabled;
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    pub fn toggle(&mut self) {
+        self.enabled = !self.enabled;
+    }
+
+}
\ No newline at end of file
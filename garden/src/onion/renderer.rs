@@ -1,4 +1,9 @@
-use crate::{garlic::Color, carrot::Renderer2D, onion::App};
+use cgmath::{Matrix4, SquareMatrix, Vector2};
+use gl::types::GLuint;
+
+use crate::{garlic::{get_gl_registry, Color, GLResource, Image, RenderTarget, Shader}, carrot::Renderer2D, mem::MutRef, warn};
+
+use super::{capture::{read_frame, FrameCapture}, Camera2D, Direction, PostEffect, TransitionKind, FULLSCREEN_VERTEX_SHADER};
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 //                                            Renderer                                            //
@@ -7,6 +12,18 @@ use crate::{garlic::Color, carrot::Renderer2D, onion::App};
 pub struct Renderer {
     pub clear_color: Color,
     pub r2d: Renderer2D,
+    width: u32,
+    height: u32,
+    targets: Vec<MutRef<RenderTarget>>,
+    cameras: Vec<Camera2D>,
+    post_effects: Vec<PostEffect>,
+    // Ping-pong targets, the scene is drawn into the first one
+    post_targets: Vec<RenderTarget>,
+    post_active: bool,
+    capture: FrameCapture,
+    transition_shader: Option<Shader>,
+    quad_vao: GLuint,
+    headless: bool,
 }
 
 impl Renderer {
@@ -15,28 +32,77 @@ impl Renderer {
         return Renderer {
             clear_color: Color::white(),
             r2d: Renderer2D::new(),
+            width: 0,
+            height: 0,
+            targets: Vec::new(),
+            cameras: Vec::new(),
+            post_effects: Vec::new(),
+            post_targets: Vec::new(),
+            post_active: false,
+            capture: FrameCapture::new(),
+            transition_shader: None,
+            quad_vao: 0,
+            headless: false,
         }
     }
 
-    pub fn init(&mut self) {
+    pub fn init(&mut self, width: u32, height: u32) {
         unsafe {
             gl::BlendFunc(gl::SRC_ALPHA, gl::ONE_MINUS_SRC_ALPHA);
             gl::Enable(gl::BLEND);
+
+            // Fullscreen passes generate their vertices in the shader
+            gl::GenVertexArrays(1, &mut self.quad_vao);
         }
+        get_gl_registry().track(GLResource::VertexArray(self.quad_vao), "Renderer");
 
         self.r2d.init();
-        self.resize(App::get().window().get_width(), App::get().window().get_height());
+        self.transition_shader = Some(Shader::load_files(FULLSCREEN_VERTEX_SHADER, "res/shaders/transition/FS.glsl"));
+        self.resize(width, height);
+    }
+
+    // No GL context, draw calls are recorded for inspection instead
+    pub fn init_headless(&mut self, width: u32, height: u32) {
+        self.headless = true;
+        self.r2d.start_recording();
+        self.resize(width, height);
     }
 
     pub fn resize(&mut self, width: u32, height: u32) {
-        unsafe {
-            gl::Viewport(0, 0, width as i32, height as i32);
+        self.width = width;
+        self.height = height;
+        // A pushed target keeps its own viewport until it is popped
+        if self.targets.is_empty() {
+            self.bind_current_target();
+        }
+    }
+
+    // Redirect the frame into the post targets when effects are enabled
+    pub fn begin_frame(&mut self) {
+        if self.headless || !self.post_effects.iter().any(|effect| effect.is_enabled()) {
+            return;
+        }
 
-            self.r2d.resize(width, height);
+        if self.post_targets.is_empty() {
+            self.post_targets.push(RenderTarget::new(self.width, self.height));
+            self.post_targets.push(RenderTarget::new(self.width, self.height));
+        }
+        for target in self.post_targets.iter_mut() {
+            target.resize(self.width, self.height);
         }
+
+        let mut scene = MutRef::from(&mut self.post_targets[0]);
+        self.push_target(&mut scene);
+        self.post_active = true;
     }
 
-    pub fn clear(&self) {
+    pub fn clear(&mut self) {
+        if self.headless {
+            // Only keep the draw calls of the current frame
+            self.r2d.clear_draw_calls();
+            return;
+        }
+
         unsafe {
             gl::ClearColor(self.clear_color.red, self.clear_color.green, self.clear_color.blue, self.clear_color.alpha);
             gl::Clear(gl::COLOR_BUFFER_BIT);
@@ -45,6 +111,305 @@ impl Renderer {
 
     pub fn render(&mut self) {
         self.r2d.render();
+
+        if self.post_active {
+            self.post_active = false;
+            self.pop_target();
+            self.apply_post_effects();
+        }
+    }
+
+    // Called before the buffers are swapped, starts & saves frame captures
+    pub fn end_frame(&mut self) {
+        if self.headless || !self.capture.is_busy() {
+            return;
+        }
+        self.capture.end_frame(self.width, self.height);
+    }
+
+    // Saves the current frame as a PNG once rendered, the file is written in the background
+    // PNGs aren't compressed, a 1080p frame takes about 8 MB
+    pub fn capture_frame(&mut self, path: &str) {
+        if self.headless {
+            warn!("Onion", "Frames can't be captured in headless mode!");
+            return;
+        }
+        self.capture.request(path);
+    }
+
+    // Saves every frame into numbered files, until stopped or the frame count is reached
+    pub fn start_capture_sequence(&mut self, directory: &str, frames: Option<u32>) {
+        if self.headless {
+            warn!("Onion", "Frames can't be captured in headless mode!");
+            return;
+        }
+        self.capture.start_sequence(directory, frames);
+    }
+
+    pub fn stop_capture_sequence(&mut self) {
+        self.capture.stop_sequence();
+    }
+
+    // Blocking read of what has been rendered so far, call after render
+    pub fn read_frame(&self) -> Option<Image> {
+        if self.headless {
+            return None;
+        }
+        return Some(read_frame(self.width, self.height));
+    }
+
+    pub fn is_capturing(&self) -> bool {
+        return self.capture.is_busy();
+    }
+
+    pub fn is_capturing_sequence(&self) -> bool {
+        return self.capture.is_capturing_sequence();
+    }
+
+    // Runs every enabled effect in order, the last pass draws on screen
+    fn apply_post_effects(&mut self) {
+        let mut remaining: usize = self.post_effects.iter().filter(|effect| effect.is_enabled()).map(|effect| effect.get_passes()).sum();
+        if remaining == 0 {
+            // Effects were disabled during the frame
+            self.post_targets[0].blit_to_screen(self.width, self.height);
+            return;
+        }
+
+        let mut source = 0;
+        unsafe {
+            gl::Disable(gl::BLEND);
+            gl::ActiveTexture(gl::TEXTURE0);
+            gl::BindVertexArray(self.quad_vao);
+        }
+        for effect in self.post_effects.iter_mut().filter(|effect| effect.is_enabled()) {
+            effect.prepare(self.width, self.height);
+            for pass in 0..effect.get_passes() {
+                remaining -= 1;
+                if remaining == 0 {
+                    RenderTarget::unbind();
+                    unsafe {
+                        gl::Viewport(0, 0, self.width as i32, self.height as i32);
+                    }
+                } else {
+                    self.post_targets[1 - source].bind();
+                }
+                effect.set_pass(pass);
+
+                unsafe {
+                    gl::BindTexture(gl::TEXTURE_2D, self.post_targets[source].get_texture());
+                    gl::DrawArrays(gl::TRIANGLES, 0, 6);
+                }
+                source = 1 - source;
+            }
+        }
+        unsafe {
+            gl::BindTexture(gl::TEXTURE_2D, 0);
+            gl::BindVertexArray(0);
+            gl::Enable(gl::BLEND);
+        }
+        Shader::unbind();
+    }
+
+    // Post Effects
+    pub fn add_post_effect(&mut self, effect: PostEffect) {
+        if self.post_effects.iter().any(|other| other.get_name() == effect.get_name()) {
+            warn!("Onion", "Post effect '{}' already added!", effect.get_name());
+            effect.dispose();
+            return;
+        }
+        self.post_effects.push(effect);
+    }
+
+    pub fn get_post_effect(&mut self, name: &str) -> Option<&mut PostEffect> {
+        return self.post_effects.iter_mut().find(|effect| effect.get_name() == name);
+    }
+
+    pub fn set_post_effect_enabled(&mut self, name: &str, enabled: bool) {
+        if let Some(effect) = self.get_post_effect(name) {
+            effect.set_enabled(enabled);
+        } else {
+            warn!("Onion", "Unknown post effect '{}'!", name);
+        }
+    }
+
+    pub fn remove_post_effect(&mut self, name: &str) {
+        if let Some(index) = self.post_effects.iter().position(|effect| effect.get_name() == name) {
+            self.post_effects.remove(index).dispose();
+        }
+    }
+
+    pub fn clear_post_effects(&mut self) {
+        for effect in self.post_effects.drain(..) {
+            effect.dispose();
+        }
+    }
+
+    // Redirect drawing into a render target until it is popped, targets can be nested
+    pub fn push_target(&mut self, target: &mut RenderTarget) {
+        if self.headless {
+            warn!("Onion", "Render targets are not available in headless mode!");
+            return;
+        }
+
+        // Pending shapes belong to the previous target
+        self.r2d.render();
+        self.targets.push(MutRef::from(target));
+        self.bind_current_target();
+    }
+
+    pub fn pop_target(&mut self) {
+        if self.headless {
+            return;
+        }
+
+        self.r2d.render();
+        if let Some(target) = self.targets.pop() {
+            target.resolve();
+        } else {
+            warn!("Onion", "No render target to pop!");
+        }
+        self.bind_current_target();
+    }
+
+    fn bind_current_target(&mut self) {
+        if let Some(target) = self.targets.last() {
+            target.bind();
+        } else if !self.headless {
+            RenderTarget::unbind();
+        }
+        let (width, height) = self.get_target_size();
+        self.r2d.resize(width, height);
+        self.apply_camera();
+    }
+
+    // Draw through a camera into its viewport until it is popped, cameras apply to the current target
+    pub fn push_camera(&mut self, camera: &Camera2D) {
+        self.r2d.render();
+        self.cameras.push(*camera);
+        self.apply_camera();
+    }
+
+    pub fn pop_camera(&mut self) {
+        self.r2d.render();
+        if self.cameras.pop().is_none() {
+            warn!("Onion", "No camera to pop!");
+        }
+        self.apply_camera();
+    }
+
+    fn apply_camera(&mut self) {
+        let (width, height) = self.get_target_size();
+        let (view, viewport) = if let Some(camera) = self.cameras.last() {
+            let viewport = camera.get_viewport().to_pixels(width, height);
+            (camera.view_matrix(viewport.2, viewport.3), viewport)
+        } else {
+            (Matrix4::identity(), (0.0, 0.0, width as f32, height as f32))
+        };
+
+        if !self.headless {
+            // GL viewports start at the bottom left
+            let (x, y, viewport_width, viewport_height) = viewport;
+            unsafe {
+                gl::Viewport(x as i32, (height as f32 - y - viewport_height) as i32, viewport_width as i32, viewport_height as i32);
+            }
+        }
+        self.r2d.set_view(view, viewport);
+    }
+
+    // Size of the render target currently drawn into
+    fn get_target_size(&self) -> (u32, u32) {
+        return self.targets.last().map_or((self.width, self.height), |target| (target.get_width(), target.get_height()));
+    }
+
+    // Conversions through a camera, screen points are in pixels of the current target
+    pub fn screen_to_world(&self, camera: &Camera2D, point: Vector2<f32>) -> Vector2<f32> {
+        let (width, height) = self.get_target_size();
+        return camera.screen_to_world(point, width, height);
+    }
+
+    pub fn world_to_screen(&self, camera: &Camera2D, point: Vector2<f32>) -> Vector2<f32> {
+        let (width, height) = self.get_target_size();
+        return camera.world_to_screen(point, width, height);
+    }
+
+    pub fn draw_transition(&mut self, from: &RenderTarget, to: &RenderTarget, progress: f64, kind: &TransitionKind) {
+        if let Some(shader) = &mut self.transition_shader {
+            shader.bind();
+            shader.load_i32("uFrom", 0);
+            shader.load_i32("uTo", 1);
+            shader.load_f32("uProgress", progress as f32);
+            match kind {
+                TransitionKind::Fade(color) => {
+                    shader.load_i32("uMode", 0);
+                    shader.load_color("uColor", color.clone());
+                },
+                TransitionKind::Crossfade => shader.load_i32("uMode", 1),
+                TransitionKind::Slide(direction) => {
+                    shader.load_i32("uMode", 2);
+                    shader.load_vec2_f32("uDirection", match direction {
+                        Direction::Left => Vector2::new(-1.0, 0.0),
+                        Direction::Right => Vector2::new(1.0, 0.0),
+                        Direction::Up => Vector2::new(0.0, 1.0),
+                        Direction::Down => Vector2::new(0.0, -1.0),
+                    });
+                },
+            }
+
+            unsafe {
+                gl::ActiveTexture(gl::TEXTURE0);
+                gl::BindTexture(gl::TEXTURE_2D, from.get_texture());
+                gl::ActiveTexture(gl::TEXTURE1);
+                gl::BindTexture(gl::TEXTURE_2D, to.get_texture());
+
+                gl::BindVertexArray(self.quad_vao);
+                gl::DrawArrays(gl::TRIANGLES, 0, 6);
+                gl::BindVertexArray(0);
+
+                gl::BindTexture(gl::TEXTURE_2D, 0);
+                gl::ActiveTexture(gl::TEXTURE0);
+                gl::BindTexture(gl::TEXTURE_2D, 0);
+            }
+            Shader::unbind();
+        }
+    }
+
+    // The renderer's context must be current
+    pub fn dispose(&mut self) {
+        if self.headless {
+            return;
+        }
+
+        self.capture.dispose();
+        self.r2d.dispose();
+        self.clear_post_effects();
+        for mut target in self.post_targets.drain(..) {
+            target.dispose();
+        }
+        if let Some(shader) = self.transition_shader.take() {
+            shader.dispose();
+        }
+        unsafe {
+            gl::DeleteVertexArrays(1, &self.quad_vao);
+        }
+        get_gl_registry().release(GLResource::VertexArray(self.quad_vao));
+        self.quad_vao = 0;
+    }
+
+    // Getters
+    pub fn get_width(&self) -> u32 {
+        return self.width;
+    }
+
+    pub fn get_height(&self) -> u32 {
+        return self.height;
+    }
+
+    pub fn get_target_depth(&self) -> usize {
+        return self.targets.len();
+    }
+
+    pub fn is_headless(&self) -> bool {
+        return self.headless;
     }
 
 }
\ No newline at end of file
@@ -1,6 +1,10 @@
-use std::any::{Any, TypeId};
+use std::{any::{Any, TypeId}, path::PathBuf};
 
-use crate::{mem::MutRef, warn};
+use crate::{mem::MutRef, tomato::Window, garlic::Effect, ui::{get_scene, set_scene, update_scene}, warn};
+
+use crate::garlic::RenderTarget;
+
+use super::{App, Renderer, Transition, TransitionKind};
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 //                                              State                                             //
@@ -14,6 +18,99 @@ pub trait IState: Any {
     fn close(&mut self);
     fn dispose(&mut self);
 
+    // Stack
+    fn pause(&mut self) {}
+    fn resume(&mut self) {}
+    // Whether the states beneath this one keep updating / rendering
+    fn updates_below(&self) -> bool { false }
+    fn renders_below(&self) -> bool { false }
+
+    // Fixed timestep
+    fn fixed_update(&mut self, _delta: f64) {}
+    fn render(&mut self, _alpha: f64) {}
+
+    // Window lifecycle
+    fn resize(&mut self, _width: u32, _height: u32) {}
+    fn focus(&mut self, _focused: bool) {}
+    fn minimize(&mut self, _minimized: bool) {}
+    fn file_drop(&mut self, _paths: &[PathBuf]) {}
+    // Return false to keep the window open
+    fn close_requested(&mut self) -> bool { true }
+
+    // Secondary windows
+    fn update_window(&mut self, _id: &str, _window: MutRef<Window>, _delta: f64) {}
+    fn close_window(&mut self, _id: &str) {}
+
+}
+
+// States that can be opened with arguments, received right before open
+pub trait IOpenWith<Args>: IState {
+
+    fn open_with(&mut self, args: Args);
+
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                         Result Handler                                         //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+// Receives the manager & the finished state's output
+type ResultCallback = Box<dyn FnOnce(&mut StateManager, Box<dyn Any>)>;
+
+struct ResultHandler {
+    state: TypeId,
+    callback: ResultCallback,
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                        Active Transition                                       //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+struct ActiveTransition {
+    kind: TransitionKind,
+    progress: Effect<f64>,
+    from: Vec<MutRef<Box<dyn IState>>>,
+    // Scene of the old states, the new ones may change it when opened
+    from_scene: Option<String>,
+    opened: bool,
+    closed: bool,
+    from_target: RenderTarget,
+    to_target: RenderTarget,
+}
+
+// Close a state & cancel the timers scoped to it
+fn close_state(mut state: MutRef<Box<dyn IState>>) {
+    state.close();
+    App::get().timers().cancel_owned((**state).type_id());
+}
+
+// Index of the lowest state reached from the top of the stack
+fn lowest(stack: &[MutRef<Box<dyn IState>>], below: fn(&dyn IState) -> bool) -> usize {
+    let mut index = stack.len().saturating_sub(1);
+    while index > 0 && below(&**stack[index]) {
+        index -= 1;
+    }
+    return index;
+}
+
+// Update and render a stack into its own target
+fn draw_states(mut renderer: MutRef<Renderer>, target: &mut RenderTarget, states: Vec<MutRef<Box<dyn IState>>>, scene: Option<String>, delta: f64, alpha: f64) {
+    renderer.push_target(target);
+    renderer.clear();
+    for mut state in states[lowest(&states, |state| state.updates_below())..].iter().copied() {
+        state.update(delta);
+    }
+    for mut state in states[lowest(&states, |state| state.renders_below())..].iter().copied() {
+        state.render(alpha);
+    }
+
+    // The side's UI fades with it
+    let current = get_scene();
+    set_scene(scene);
+    update_scene(delta);
+    set_scene(current);
+    renderer.render();
+    renderer.pop_target();
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -22,7 +119,9 @@ pub trait IState: Any {
 
 pub struct StateManager {
     states: Vec<Box<dyn IState>>,
-    current: Option<MutRef<Box<dyn IState>>>,
+    stack: Vec<MutRef<Box<dyn IState>>>,
+    transition: Option<ActiveTransition>,
+    results: Vec<ResultHandler>,
     initialized: bool,
     disposed: bool
 }
@@ -32,7 +131,9 @@ impl StateManager {
     pub fn new() -> StateManager {
         return StateManager {
             states: vec![],
-            current: None,
+            stack: vec![],
+            transition: None,
+            results: vec![],
             initialized: false,
             disposed: false
         }
@@ -69,8 +170,12 @@ impl StateManager {
     pub fn init(&mut self) {
         if !self.initialized {
             self.states.iter_mut().for_each(|state| state.init());
-            if let Some(current) = &mut self.current {
-                current.open();
+            let depth = self.stack.len();
+            for (index, state) in self.stack.iter_mut().enumerate() {
+                state.open();
+                if index + 1 < depth {
+                    state.pause();
+                }
             }
             self.initialized = true;
         } else {
@@ -78,29 +183,350 @@ impl StateManager {
         }
     }
 
+    fn is_active(&self) -> bool {
+        return self.initialized && !self.disposed;
+    }
+
     pub fn open<State: IState + 'static>(&mut self) {
         self.close();
+        self.push::<State>();
+    }
+
+    pub fn open_with<State: IOpenWith<Args> + 'static, Args>(&mut self, args: Args) {
+        self.close();
+        self.push_with::<State, Args>(args);
+    }
+
+    pub fn push_with<State: IOpenWith<Args> + 'static, Args>(&mut self, args: Args) {
+        if self.is_open::<State>() {
+            warn!("Onion", "State '{}' is already open!", std::any::type_name::<State>());
+            return;
+        }
+
+        if let Some(mut state) = self.get_state::<State>() {
+            state.open_with(args);
+        }
+        self.push::<State>();
+    }
+
+    // Called with the opener when State finishes with a result, dropped if it closes without one
+    pub fn on_result<State: IState + 'static, Opener: IState + 'static, Output: 'static>(&mut self, callback: impl FnOnce(&mut Opener, Output) + 'static) {
+        self.results.retain(|handler| handler.state != TypeId::of::<State>());
+        self.results.push(ResultHandler {
+            state: TypeId::of::<State>(),
+            callback: Box::new(move |manager, output| {
+                match output.downcast::<Output>() {
+                    Ok(output) => {
+                        if let Some(mut opener) = manager.get_state::<Opener>() {
+                            callback(&mut opener, *output);
+                        }
+                    },
+                    Err(_) => warn!("Onion", "State '{}' finished with an unexpected result type, expected '{}'!", std::any::type_name::<State>(), std::any::type_name::<Output>()),
+                }
+            }),
+        });
+    }
+
+    fn take_result_handler(&mut self, state: TypeId) -> Option<ResultHandler> {
+        let index = self.results.iter().position(|handler| handler.state == state)?;
+        return Some(self.results.remove(index));
+    }
+
+    // Pop the top state and hand its result to the handler registered for it
+    pub fn finish<Output: 'static>(&mut self, output: Output) {
+        self.finish_transition();
+        if let Some(top) = self.stack.pop() {
+            let handler = self.take_result_handler((**top).type_id());
+            if self.is_active() {
+                close_state(top);
+                if let Some(handler) = handler {
+                    (handler.callback)(self, Box::new(output));
+                }
+                if let Some(top) = self.stack.last_mut() {
+                    top.resume();
+                }
+            }
+        } else {
+            warn!("Onion", "Failed to finish state, no state open!");
+        }
+    }
+
+    // Replace the stack, animating from the old states to the new one
+    pub fn open_with_transition<State: IState + 'static>(&mut self, transition: Transition) {
+        // Headless renderers have no targets to animate with
+        if !self.is_active() || self.is_open::<State>() || App::get().renderer().is_headless() {
+            self.open::<State>();
+            return;
+        }
+        self.finish_transition();
+
+        let state = self.get_state_ref::<State>();
+        if let Some(state) = state {
+            let renderer = App::get().renderer();
+            let (width, height) = (renderer.get_width(), renderer.get_height());
+            let mut progress = Effect::new(0.0);
+            progress.set_animation(0.0, 1.0, transition.duration, transition.mode, false).start();
+            self.transition = Some(ActiveTransition {
+                kind: transition.kind,
+                progress,
+                from: std::mem::replace(&mut self.stack, vec![state]),
+                from_scene: get_scene(),
+                opened: false,
+                closed: false,
+                from_target: RenderTarget::new(width, height),
+                to_target: RenderTarget::new(width, height),
+            });
+            self.step_transition();
+        }
+    }
+
+    pub fn is_transitioning(&self) -> bool {
+        return self.transition.is_some();
+    }
+
+    // Close the old states and open the new one once the transition reaches their points
+    fn step_transition(&mut self) {
+        let mut finished = false;
+        if let Some(transition) = &mut self.transition {
+            let progress = transition.progress.current();
+            finished = !transition.progress.is_running();
+            if !transition.closed && (finished || progress >= transition.kind.close_point()) {
+                while let Some(state) = transition.from.pop() {
+                    let closed = (**state).type_id();
+                    self.results.retain(|handler| handler.state != closed);
+                    close_state(state);
+                }
+                transition.closed = true;
+            }
+            if !transition.opened && (finished || progress >= transition.kind.open_point()) {
+                transition.from_scene = get_scene();
+                self.stack.iter_mut().for_each(|state| state.open());
+                transition.opened = true;
+            }
+            if finished {
+                transition.from_target.dispose();
+                transition.to_target.dispose();
+            }
+        }
+        if finished {
+            self.transition = None;
+        }
+    }
+
+    fn finish_transition(&mut self) {
+        if let Some(transition) = &mut self.transition {
+            transition.progress.stop();
+        }
+        self.step_transition();
+    }
+
+    fn update_transition(&mut self, delta: f64) {
+        if let Some(transition) = &mut self.transition {
+            transition.progress.update();
+        }
+        self.step_transition();
+
+        let renderer = App::get().renderer();
+        let alpha = App::get().get_alpha();
+        // Both sides render into their own target, then get composited on screen
+        if let Some(transition) = &mut self.transition {
+            transition.from_target.resize(renderer.get_width(), renderer.get_height());
+            transition.to_target.resize(renderer.get_width(), renderer.get_height());
+            if !transition.closed {
+                let scene = if transition.opened { transition.from_scene.clone() } else { get_scene() };
+                draw_states(renderer, &mut transition.from_target, transition.from.clone(), scene, delta, alpha);
+            }
+        }
+        if let Some(transition) = &mut self.transition {
+            if transition.opened {
+                draw_states(renderer, &mut transition.to_target, self.stack.clone(), get_scene(), delta, alpha);
+            }
+        }
+        if let Some(transition) = &self.transition {
+            let mut renderer = renderer;
+            renderer.draw_transition(&transition.from_target, &transition.to_target, transition.progress.current(), &transition.kind);
+        }
+    }
+
+    pub fn push<State: IState + 'static>(&mut self) {
+        self.finish_transition();
+        if self.stack.iter().any(|state| (***state).type_id() == TypeId::of::<State>()) {
+            warn!("Onion", "State '{}' is already open!", std::any::type_name::<State>());
+            return;
+        }
+
         let state = self.get_state_ref::<State>();
         if let Some(mut state) = state {
-            if self.initialized && !self.disposed {
+            if self.is_active() {
+                if let Some(top) = self.stack.last_mut() {
+                    top.pause();
+                }
                 state.open();
             }
-            self.current = Some(state);
+            self.stack.push(state);
+        }
+    }
+
+    pub fn pop(&mut self) {
+        self.finish_transition();
+        if let Some(top) = self.stack.pop() {
+            self.take_result_handler((**top).type_id());
+            if self.is_active() {
+                close_state(top);
+                if let Some(top) = self.stack.last_mut() {
+                    top.resume();
+                }
+            }
+        } else {
+            warn!("Onion", "Failed to pop state, no state open!");
         }
     }
 
+    pub fn depth(&self) -> usize {
+        return self.stack.len();
+    }
+
+    pub fn is_open<State: IState + 'static>(&self) -> bool {
+        return self.stack.iter().any(|state| (***state).type_id() == TypeId::of::<State>());
+    }
+
     pub fn update(&mut self, delta: f64) {
+        if self.is_active() {
+            if self.transition.is_some() {
+                self.update_transition(delta);
+                // Fall through on the frame the transition ends
+                if self.transition.is_some() {
+                    return;
+                }
+            }
+
+            // States may be pushed or popped while iterating
+            let mut index = lowest(&self.stack, |state| state.updates_below());
+            while index < self.stack.len() {
+                let mut state = self.stack[index];
+                state.update(delta);
+                index += 1;
+            }
+        }
+    }
+
+    pub fn fixed_update(&mut self, delta: f64) {
+        if self.is_active() {
+            if let Some(transition) = &self.transition {
+                let mut sides = vec![];
+                if !transition.closed {
+                    sides.push(transition.from.clone());
+                }
+                if transition.opened {
+                    sides.push(self.stack.clone());
+                }
+                for states in sides {
+                    for mut state in states[lowest(&states, |state| state.updates_below())..].iter().copied() {
+                        state.fixed_update(delta);
+                    }
+                }
+                return;
+            }
+
+            // States may be pushed or popped while iterating
+            let mut index = lowest(&self.stack, |state| state.updates_below());
+            while index < self.stack.len() {
+                let mut state = self.stack[index];
+                state.fixed_update(delta);
+                index += 1;
+            }
+        }
+    }
+
+    pub fn render(&mut self, alpha: f64) {
+        // Transitions render both sides while updating
+        if self.is_active() && self.transition.is_none() {
+            // States may be pushed or popped while iterating
+            let mut index = lowest(&self.stack, |state| state.renders_below());
+            while index < self.stack.len() {
+                let mut state = self.stack[index];
+                state.render(alpha);
+                index += 1;
+            }
+        }
+    }
+
+    // The incoming states of a transition are skipped until opened, the outgoing ones once closed
+    fn open_states(&self) -> Vec<MutRef<Box<dyn IState>>> {
+        return match &self.transition {
+            Some(transition) => {
+                let mut states = vec![];
+                if !transition.closed {
+                    states.extend(transition.from.iter().copied());
+                }
+                if transition.opened {
+                    states.extend(self.stack.iter().copied());
+                }
+                states
+            },
+            None => self.stack.clone(),
+        }
+    }
+
+    // Resize, focus & minimize reach every open state, top first
+    pub fn resize(&mut self, width: u32, height: u32) {
+        if self.is_active() {
+            self.open_states().iter_mut().rev().for_each(|state| state.resize(width, height));
+        }
+    }
+
+    pub fn focus(&mut self, focused: bool) {
+        if self.is_active() {
+            self.open_states().iter_mut().rev().for_each(|state| state.focus(focused));
+        }
+    }
+
+    pub fn minimize(&mut self, minimized: bool) {
+        if self.is_active() {
+            self.open_states().iter_mut().rev().for_each(|state| state.minimize(minimized));
+        }
+    }
+
+    // File drops & close requests go to the top state
+    pub fn file_drop(&mut self, paths: &[PathBuf]) {
+        if self.is_active() {
+            if let Some(mut top) = self.open_states().last().copied() {
+                top.file_drop(paths);
+            }
+        }
+    }
+
+    pub fn close_requested(&mut self) -> bool {
+        if self.is_active() {
+            if let Some(mut top) = self.open_states().last().copied() {
+                return top.close_requested();
+            }
+        }
+        return true;
+    }
+
+    pub fn update_window(&mut self, owner: TypeId, id: &str, window: MutRef<Window>, delta: f64) {
+        if self.initialized && !self.disposed {
+            if let Some(state) = self.states.iter_mut().find(|state| (***state).type_id() == owner) {
+                state.update_window(id, window, delta);
+            }
+        }
+    }
+
+    pub fn close_window(&mut self, owner: TypeId, id: &str) {
         if self.initialized && !self.disposed {
-            if let Some(current) = &mut self.current {
-                current.update(delta);
+            if let Some(state) = self.states.iter_mut().find(|state| (***state).type_id() == owner) {
+                state.close_window(id);
             }
         }
     }
 
     pub fn close(&mut self) {
-        if let Some(mut current) = self.current.take() {
-            if self.initialized && !self.disposed {
-                current.close();
+        self.finish_transition();
+        while let Some(top) = self.stack.pop() {
+            self.take_result_handler((**top).type_id());
+            if self.is_active() {
+                close_state(top);
             }
         }
     }
@@ -0,0 +1,300 @@
+use std::{
+    future::Future,
+    panic::{self, AssertUnwindSafe},
+    pin::Pin,
+    sync::{atomic::{AtomicBool, Ordering}, mpsc::{self, Receiver, Sender}, Arc, Mutex, OnceLock},
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, JoinHandle},
+};
+
+use crate::{critical, error, debug};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                           Job Handle                                           //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+enum JobResult<T> {
+    Running(Option<Waker>),
+    Done(T),
+    Panicked,
+    Taken
+}
+
+// Resolves on the main thread to the job's result, None if the job panicked
+pub struct JobHandle<T> {
+    result: Arc<Mutex<JobResult<T>>>
+}
+
+impl<T> JobHandle<T> {
+
+    pub fn is_done(&self) -> bool {
+        return !matches!(*self.result.lock().unwrap(), JobResult::Running(_));
+    }
+
+}
+
+impl<T> Future for JobHandle<T> {
+    type Output = Option<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut result = self.result.lock().unwrap();
+        return match std::mem::replace(&mut *result, JobResult::Taken) {
+            JobResult::Running(_) => {
+                *result = JobResult::Running(Some(cx.waker().clone()));
+                Poll::Pending
+            },
+            JobResult::Done(value) => Poll::Ready(Some(value)),
+            JobResult::Panicked | JobResult::Taken => Poll::Ready(None),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                            Job Pool                                            //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+type Job = Box<dyn FnOnce() + Send>;
+
+struct JobPool {
+    sender: Option<Sender<Job>>,
+    workers: Vec<JoinHandle<()>>
+}
+
+impl JobPool {
+
+    fn new() -> JobPool {
+        // Leave a core to the main thread
+        let count = thread::available_parallelism().map_or(1, |count| count.get().saturating_sub(1).max(1));
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+
+        let workers = (0..count).map(|index| {
+            let receiver: Arc<Mutex<Receiver<Job>>> = receiver.clone();
+            thread::Builder::new().name(format!("garden-worker-{}", index)).spawn(move || loop {
+                let job = receiver.lock().unwrap().recv();
+                match job {
+                    Ok(job) => job(),
+                    // The pool was disposed
+                    Err(_) => break,
+                }
+            }).unwrap_or_else(|_| critical!("Onion", "Failed to spawn worker thread {}!", index))
+        }).collect();
+
+        debug!("Onion", "Started job pool with {} workers", count);
+        return JobPool {
+            sender: Some(sender),
+            workers
+        }
+    }
+
+    fn dispose(&mut self) {
+        // Closing the channel stops the workers once the queued jobs are done
+        self.sender = None;
+        for worker in self.workers.drain(..) {
+            if worker.join().is_err() {
+                error!("Onion", "Failed to join worker thread!");
+            }
+        }
+    }
+
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                         Task Scheduler                                         //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+// Wakes up the app's event loop, shared by every task of a scheduler
+type LoopWaker = Arc<OnceLock<fn()>>;
+
+struct TaskWaker {
+    woken: AtomicBool,
+    loop_waker: LoopWaker
+}
+
+impl Wake for TaskWaker {
+
+    fn wake(self: Arc<Self>) {
+        self.woken.store(true, Ordering::Release);
+        if let Some(wake_loop) = self.loop_waker.get() {
+            wake_loop();
+        }
+    }
+
+}
+
+struct Task {
+    future: Pin<Box<dyn Future<Output = ()>>>,
+    waker: Arc<TaskWaker>
+}
+
+pub struct TaskScheduler {
+    tasks: Vec<Task>,
+    // Spawned while polling, added on the next poll
+    spawned: Vec<Task>,
+    pool: Option<JobPool>,
+    loop_waker: LoopWaker
+}
+
+impl Default for TaskScheduler {
+
+    fn default() -> Self {
+        return TaskScheduler::new();
+    }
+
+}
+
+impl TaskScheduler {
+
+    pub fn new() -> TaskScheduler {
+        return TaskScheduler {
+            tasks: Vec::new(),
+            spawned: Vec::new(),
+            pool: None,
+            loop_waker: Arc::new(OnceLock::new())
+        }
+    }
+
+    // Called from any thread when a task is woken, so a waiting event loop can poll it
+    pub fn set_loop_waker(&mut self, wake_loop: fn()) {
+        if self.loop_waker.set(wake_loop).is_err() {
+            error!("Onion", "The task scheduler's loop waker is already set!");
+        }
+    }
+
+    // Runs on the main thread, polled once per frame when woken
+    pub fn spawn<F: Future<Output = ()> + 'static>(&mut self, future: F) {
+        self.spawned.push(Task {
+            future: Box::pin(future),
+            waker: Arc::new(TaskWaker {
+                woken: AtomicBool::new(true),
+                loop_waker: self.loop_waker.clone()
+            })
+        });
+    }
+
+    // Runs on a worker thread, GL & the UI system must not be touched from the job
+    pub fn spawn_job<T: Send + 'static, F: FnOnce() -> T + Send + 'static>(&mut self, job: F) -> JobHandle<T> {
+        let result = Arc::new(Mutex::new(JobResult::Running(None)));
+        let job_result = result.clone();
+        let job: Job = Box::new(move || {
+            let value = panic::catch_unwind(AssertUnwindSafe(job));
+            let mut result = job_result.lock().unwrap();
+            let previous = std::mem::replace(&mut *result, match value {
+                Ok(value) => JobResult::Done(value),
+                Err(_) => JobResult::Panicked,
+            });
+            if let JobResult::Running(Some(waker)) = previous {
+                waker.wake();
+            }
+        });
+
+        if self.pool.get_or_insert_with(JobPool::new).sender.as_ref().unwrap().send(job).is_err() {
+            error!("Onion", "Failed to queue job, the job pool has stopped!");
+        }
+        return JobHandle {
+            result
+        }
+    }
+
+    // Runs the job on a worker thread & the callback on the main thread
+    pub fn spawn_job_then<T: Send + 'static, F: FnOnce() -> T + Send + 'static, C: FnOnce(T) + 'static>(&mut self, job: F, callback: C) {
+        let handle = self.spawn_job(job);
+        self.spawn(async move {
+            match handle.await {
+                Some(value) => callback(value),
+                None => error!("Onion", "Job panicked, its callback won't be called!"),
+            }
+        });
+    }
+
+    pub fn poll(&mut self) {
+        self.tasks.append(&mut self.spawned);
+
+        let mut index = 0;
+        while index < self.tasks.len() {
+            let task = &mut self.tasks[index];
+            if !task.waker.woken.swap(false, Ordering::AcqRel) {
+                index += 1;
+                continue;
+            }
+
+            let waker = Waker::from(task.waker.clone());
+            let mut context = Context::from_waker(&waker);
+            if task.future.as_mut().poll(&mut context).is_ready() {
+                self.tasks.remove(index);
+            } else {
+                index += 1;
+            }
+        }
+    }
+
+    // Whether tasks are waiting
+    pub fn is_busy(&self) -> bool {
+        return !self.tasks.is_empty() || !self.spawned.is_empty();
+    }
+
+    // Whether a task was woken since the last poll & needs to be polled again
+    pub fn has_woken_tasks(&self) -> bool {
+        return self.tasks.iter().chain(self.spawned.iter()).any(|task| task.waker.woken.load(Ordering::Acquire));
+    }
+
+    pub fn dispose(&mut self) {
+        self.tasks.clear();
+        self.spawned.clear();
+        if let Some(pool) = &mut self.pool {
+            pool.dispose();
+        }
+        self.pool = None;
+    }
+
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                              Tests                                             //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc, sync::atomic::AtomicUsize, time::{Duration, Instant}};
+
+    use super::*;
+
+    static LOOP_WAKES: AtomicUsize = AtomicUsize::new(0);
+
+    fn wake_loop() {
+        LOOP_WAKES.fetch_add(1, Ordering::SeqCst);
+    }
+
+    #[test]
+    fn completed_jobs_wake_their_task() {
+        let mut tasks = TaskScheduler::new();
+        tasks.set_loop_waker(wake_loop);
+
+        let (sender, receiver) = mpsc::channel::<i32>();
+        let result = Rc::new(Cell::new(None));
+        let callback_result = result.clone();
+        tasks.spawn_job_then(move || receiver.recv().unwrap() * 2, move |value| callback_result.set(Some(value)));
+
+        // The job is blocked, polling leaves the task waiting without waking it
+        assert!(tasks.has_woken_tasks());
+        tasks.poll();
+        assert!(tasks.is_busy());
+        assert!(!tasks.has_woken_tasks());
+
+        sender.send(21).unwrap();
+        let start = Instant::now();
+        while !tasks.has_woken_tasks() {
+            assert!(start.elapsed() < Duration::from_secs(5), "The job never woke its task");
+            thread::sleep(Duration::from_millis(1));
+        }
+        assert!(LOOP_WAKES.load(Ordering::SeqCst) > 0);
+
+        // The callback runs on the next poll
+        assert_eq!(result.get(), None);
+        tasks.poll();
+        assert_eq!(result.get(), Some(42));
+        assert!(!tasks.is_busy());
+        tasks.dispose();
+    }
+
+}
\ No newline at end of file
@@ -0,0 +1,149 @@
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                              Time                                              //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct Time {
+    // Window clock at the start of the current frame
+    real_time: f64,
+    // Scaled time, frozen while paused
+    time: f64,
+    delta: f64,
+    unscaled_delta: f64,
+    scale: f64,
+    paused: bool,
+    frame_count: u64
+}
+
+impl Default for Time {
+
+    fn default() -> Self {
+        return Time::new();
+    }
+
+}
+
+impl Time {
+
+    pub fn new() -> Time {
+        return Time {
+            real_time: 0.0,
+            time: 0.0,
+            delta: 0.0,
+            unscaled_delta: 0.0,
+            scale: 1.0,
+            paused: false,
+            frame_count: 0
+        }
+    }
+
+    // Restart the frame clock without counting the time since the last frame
+    pub(crate) fn reset(&mut self, now: f64) {
+        self.real_time = now;
+        self.delta = 0.0;
+        self.unscaled_delta = 0.0;
+    }
+
+    pub(crate) fn advance(&mut self, now: f64) {
+        self.unscaled_delta = (now - self.real_time).max(0.0);
+        self.real_time = now;
+        self.delta = if self.paused { 0.0 } else { self.unscaled_delta * self.scale };
+        self.time += self.delta;
+        self.frame_count += 1;
+    }
+
+    // Real duration of a scaled duration, None while time is frozen
+    pub fn to_real(&self, duration: f64) -> Option<f64> {
+        if self.paused || self.scale <= 0.0 {
+            return None;
+        }
+        return Some(duration / self.scale);
+    }
+
+    // Getters
+    pub fn get_real_time(&self) -> f64 {
+        return self.real_time;
+    }
+
+    pub fn get_time(&self) -> f64 {
+        return self.time;
+    }
+
+    pub fn get_delta(&self) -> f64 {
+        return self.delta;
+    }
+
+    pub fn get_unscaled_delta(&self) -> f64 {
+        return self.unscaled_delta;
+    }
+
+    pub fn get_scale(&self) -> f64 {
+        return self.scale;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        return self.paused;
+    }
+
+    pub fn get_frame_count(&self) -> u64 {
+        return self.frame_count;
+    }
+
+    // Setters
+    pub fn set_scale(&mut self, scale: f64) {
+        self.scale = scale.max(0.0);
+    }
+
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                              Tests                                             //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scaled_time() {
+        let mut time = Time::default();
+        time.reset(10.0);
+        time.set_scale(0.5);
+        time.advance(10.2);
+        assert!((time.get_delta() - 0.1).abs() < 1e-9);
+        assert!((time.get_unscaled_delta() - 0.2).abs() < 1e-9);
+        assert!((time.get_time() - 0.1).abs() < 1e-9);
+        assert_eq!(time.get_real_time(), 10.2);
+        assert_eq!(time.to_real(1.0), Some(2.0));
+
+        // Negative scales are clamped, the clock never runs backwards
+        time.set_scale(-1.0);
+        assert_eq!(time.get_scale(), 0.0);
+        assert_eq!(time.to_real(1.0), None);
+        time.advance(10.0);
+        assert_eq!(time.get_unscaled_delta(), 0.0);
+    }
+
+    #[test]
+    fn paused_time() {
+        let mut time = Time::new();
+        time.advance(1.0);
+        time.set_paused(true);
+        assert!(time.is_paused());
+        time.advance(2.0);
+        assert_eq!(time.get_delta(), 0.0);
+        assert_eq!(time.get_unscaled_delta(), 1.0);
+        assert_eq!(time.get_time(), 1.0);
+        assert_eq!(time.to_real(1.0), None);
+
+        // Frames are still counted while paused
+        time.set_paused(false);
+        time.advance(2.5);
+        assert_eq!(time.get_time(), 1.5);
+        assert_eq!(time.get_frame_count(), 3);
+    }
+
+}
\ No newline at end of file
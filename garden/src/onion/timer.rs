@@ -0,0 +1,240 @@
+use std::any::TypeId;
+
+use super::{App, IState};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                          Timer Handle                                          //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct TimerHandle(u64);
+
+impl TimerHandle {
+
+    pub fn cancel(self) {
+        App::get().timers().cancel(self);
+    }
+
+    pub fn is_active(self) -> bool {
+        return App::get().timers().is_active(self);
+    }
+
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                             Timers                                             //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+// Shortest repeat interval, avoids firing forever within a single frame
+const MIN_INTERVAL: f64 = 0.001;
+
+struct Timer {
+    handle: TimerHandle,
+    remaining: f64,
+    interval: Option<f64>,
+    callback: Box<dyn FnMut()>,
+    // Cancelled when this state closes
+    owner: Option<TypeId>,
+    cancelled: bool
+}
+
+pub struct Timers {
+    timers: Vec<Timer>,
+    // Added while updating, merged on the next update
+    added: Vec<Timer>,
+    next_id: u64
+}
+
+impl Default for Timers {
+
+    fn default() -> Self {
+        return Timers::new();
+    }
+
+}
+
+impl Timers {
+
+    pub fn new() -> Timers {
+        return Timers {
+            timers: Vec::new(),
+            added: Vec::new(),
+            next_id: 0
+        }
+    }
+
+    fn add<F: FnMut() + 'static>(&mut self, delay: f64, interval: Option<f64>, owner: Option<TypeId>, callback: F) -> TimerHandle {
+        let handle = TimerHandle(self.next_id);
+        self.next_id += 1;
+        self.added.push(Timer {
+            handle,
+            remaining: delay.max(0.0),
+            interval: interval.map(|interval| interval.max(MIN_INTERVAL)),
+            callback: Box::new(callback),
+            owner,
+            cancelled: false
+        });
+        return handle;
+    }
+
+    // Durations are in scaled seconds
+    pub fn after<F: FnOnce() + 'static>(&mut self, duration: f64, callback: F) -> TimerHandle {
+        let mut callback = Some(callback);
+        return self.add(duration, None, None, move || if let Some(callback) = callback.take() { callback() });
+    }
+
+    pub fn every<F: FnMut() + 'static>(&mut self, interval: f64, callback: F) -> TimerHandle {
+        return self.add(interval, Some(interval), None, callback);
+    }
+
+    // Cancelled automatically when State closes
+    pub fn after_in<State: IState + 'static, F: FnOnce() + 'static>(&mut self, duration: f64, callback: F) -> TimerHandle {
+        let mut callback = Some(callback);
+        return self.add(duration, None, Some(TypeId::of::<State>()), move || if let Some(callback) = callback.take() { callback() });
+    }
+
+    pub fn every_in<State: IState + 'static, F: FnMut() + 'static>(&mut self, interval: f64, callback: F) -> TimerHandle {
+        return self.add(interval, Some(interval), Some(TypeId::of::<State>()), callback);
+    }
+
+    pub fn cancel(&mut self, handle: TimerHandle) {
+        self.timers.iter_mut().chain(self.added.iter_mut())
+            .filter(|timer| timer.handle == handle)
+            .for_each(|timer| timer.cancelled = true);
+    }
+
+    pub(crate) fn cancel_owned(&mut self, owner: TypeId) {
+        self.timers.iter_mut().chain(self.added.iter_mut())
+            .filter(|timer| timer.owner == Some(owner))
+            .for_each(|timer| timer.cancelled = true);
+    }
+
+    pub fn is_active(&self, handle: TimerHandle) -> bool {
+        return self.timers.iter().chain(self.added.iter()).any(|timer| timer.handle == handle && !timer.cancelled);
+    }
+
+    // Scaled seconds until the next timer fires
+    pub fn next_due(&self) -> Option<f64> {
+        return self.timers.iter().chain(self.added.iter())
+            .filter(|timer| !timer.cancelled)
+            .map(|timer| timer.remaining)
+            .reduce(f64::min);
+    }
+
+    pub fn update(&mut self, delta: f64) {
+        self.timers.append(&mut self.added);
+
+        // Callbacks may add or cancel timers while iterating
+        for index in 0..self.timers.len() {
+            let timer = &mut self.timers[index];
+            if timer.cancelled {
+                continue;
+            }
+
+            timer.remaining -= delta;
+            while timer.remaining <= 0.0 && !timer.cancelled {
+                if let Some(interval) = timer.interval {
+                    timer.remaining += interval;
+                } else {
+                    timer.cancelled = true;
+                }
+                (timer.callback)();
+            }
+        }
+
+        self.timers.retain(|timer| !timer.cancelled);
+    }
+
+    pub fn clear(&mut self) {
+        self.timers.clear();
+        self.added.clear();
+    }
+
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                              Tests                                             //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc};
+
+    use super::*;
+
+    struct Owner;
+
+    impl IState for Owner {
+        fn init(&mut self) {}
+        fn open(&mut self) {}
+        fn update(&mut self, _delta: f64) {}
+        fn close(&mut self) {}
+        fn dispose(&mut self) {}
+    }
+
+    fn counter() -> (Rc<Cell<u32>>, impl FnMut() + 'static) {
+        let count = Rc::new(Cell::new(0));
+        let inner = count.clone();
+        return (count, move || inner.set(inner.get() + 1));
+    }
+
+    #[test]
+    fn after_fires_once() {
+        let mut timers = Timers::default();
+        let (count, callback) = counter();
+        let handle = timers.after(1.0, callback);
+        assert!(timers.is_active(handle));
+
+        timers.update(0.5);
+        assert_eq!(count.get(), 0);
+        assert_eq!(timers.next_due(), Some(0.5));
+
+        timers.update(0.5);
+        timers.update(5.0);
+        assert_eq!(count.get(), 1);
+        assert!(!timers.is_active(handle));
+        assert_eq!(timers.next_due(), None);
+    }
+
+    #[test]
+    fn every_catches_up() {
+        let mut timers = Timers::new();
+        let (count, callback) = counter();
+        timers.every(0.25, callback);
+
+        timers.update(0.2);
+        assert_eq!(count.get(), 0);
+        // Long frames fire every missed interval
+        timers.update(1.0);
+        assert_eq!(count.get(), 4);
+        assert!((timers.next_due().unwrap() - 0.05).abs() < 1e-9);
+    }
+
+    #[test]
+    fn cancel() {
+        let mut timers = Timers::new();
+        let (count, callback) = counter();
+        let handle = timers.every(0.1, callback);
+        let (owned_count, owned_callback) = counter();
+        timers.every_in::<Owner, _>(0.1, owned_callback);
+
+        timers.update(0.1);
+        assert_eq!((count.get(), owned_count.get()), (1, 1));
+
+        timers.cancel(handle);
+        timers.cancel_owned(TypeId::of::<Owner>());
+        timers.update(1.0);
+        assert_eq!((count.get(), owned_count.get()), (1, 1));
+        assert_eq!(timers.next_due(), None);
+
+        // Paused time (a zero delta) never fires
+        let (count, callback) = counter();
+        timers.after(0.5, callback);
+        timers.update(0.0);
+        assert_eq!(count.get(), 0);
+        timers.clear();
+        timers.update(1.0);
+        assert_eq!(count.get(), 0);
+    }
+
+}
\ No newline at end of file
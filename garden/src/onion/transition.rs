@@ -0,0 +1,79 @@
+use crate::garlic::{Color, EffectMode};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                         Transition Kind                                        //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum Direction {
+    Left,
+    Right,
+    Up,
+    Down
+}
+
+#[derive(Clone)]
+pub enum TransitionKind {
+    Fade(Color),
+    Crossfade,
+    Slide(Direction)
+}
+
+impl TransitionKind {
+
+    // Progress at which the new state is opened
+    pub fn open_point(&self) -> f64 {
+        return match self {
+            TransitionKind::Fade(_) => 0.5,
+            TransitionKind::Crossfade | TransitionKind::Slide(_) => 0.0,
+        }
+    }
+
+    // Progress at which the old states are closed
+    pub fn close_point(&self) -> f64 {
+        return match self {
+            TransitionKind::Fade(_) => 0.5,
+            TransitionKind::Crossfade | TransitionKind::Slide(_) => 1.0,
+        }
+    }
+
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                           Transition                                           //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct Transition {
+    pub kind: TransitionKind,
+    pub duration: f64,
+    pub mode: EffectMode
+}
+
+impl Transition {
+
+    pub fn new(kind: TransitionKind, duration: f64) -> Transition {
+        return Transition {
+            kind,
+            duration,
+            mode: EffectMode::EaseInOut
+        }
+    }
+
+    pub fn fade(color: Color, duration: f64) -> Transition {
+        return Transition::new(TransitionKind::Fade(color), duration);
+    }
+
+    pub fn crossfade(duration: f64) -> Transition {
+        return Transition::new(TransitionKind::Crossfade, duration);
+    }
+
+    pub fn slide(direction: Direction, duration: f64) -> Transition {
+        return Transition::new(TransitionKind::Slide(direction), duration);
+    }
+
+    pub fn with_mode(mut self, mode: EffectMode) -> Transition {
+        self.mode = mode;
+        return self;
+    }
+
+}
\ No newline at end of file
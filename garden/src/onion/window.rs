@@ -0,0 +1,27 @@
+use std::any::TypeId;
+
+use crate::{tomato::Window, onion::Renderer};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                           App Window                                           //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct AppWindow {
+    pub id: String,
+    pub owner: TypeId,
+    pub window: Window,
+    pub renderer: Renderer,
+}
+
+impl AppWindow {
+
+    pub fn new(id: String, owner: TypeId, window: Window) -> AppWindow {
+        return AppWindow {
+            id,
+            owner,
+            window,
+            renderer: Renderer::new()
+        }
+    }
+
+}
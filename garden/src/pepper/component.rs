@@ -50,4 +50,7 @@ pub struct ZIndexComponent(pub i32);
 pub struct ParentComponent(pub Option<String>);
 
 #[derive(Clone)]
-pub struct SceneComponent(pub Option<String>);
\ No newline at end of file
+pub struct SceneComponent(pub Option<String>);
+
+#[derive(Clone)]
+pub struct WindowComponent(pub Option<String>);
\ No newline at end of file
@@ -986,7 +986,9 @@ mod util {
         return element_x + (element_width - self_width) / 2.0 + offset;
     }
 
-    pub fn pixel_relative(value: f32, axis: Axis, mut relative_constraints: MutRef<Constraints>) -> f32 {
+    // Scales a logical pixel value with another element, then converts it to framebuffer pixels
+    pub fn pixel_relative(value: f32, axis: Axis, mut relative_constraints: MutRef<Constraints>, screen: Ref<ScreenProperty>) -> f32 {
+        let value = screen_pixel(value, axis, screen);
         match axis {
             Axis::X => value * relative_constraints.get_scale_x(),
             Axis::Y => value * relative_constraints.get_scale_y(),
@@ -996,7 +998,20 @@ mod util {
         }
     }
 
+    // Converts a logical pixel value to framebuffer pixels
+    pub fn screen_pixel(value: f32, axis: Axis, screen: Ref<ScreenProperty>) -> f32 {
+        match axis {
+            Axis::X => value * screen.content_scale_x,
+            Axis::Y => value * screen.content_scale_y,
+            Axis::Min => value * screen.content_scale_x.min(screen.content_scale_y),
+            Axis::Max => value * screen.content_scale_x.max(screen.content_scale_y),
+            Axis::Average => value * (screen.content_scale_x + screen.content_scale_y) / 2.0,
+        }
+    }
+
+    // Scales a logical pixel value with the window size
     pub fn screen_pixel_relative(value: f32, axis: Axis, screen: Ref<ScreenProperty>) -> f32 {
+        let value = screen_pixel(value, axis, screen);
         match axis {
             Axis::X => value * screen.scale_x,
             Axis::Y => value * screen.scale_y,
@@ -1172,10 +1187,10 @@ impl Constraints {
                                         match relative {
                                             ElementRef::This => {
                                                 let constraints = MutRef::new(self as *mut Constraints);
-                                                util::from_left(util::pixel_relative(value, axis, constraints), element_constraints.get_x(), self.get_width())
+                                                util::from_left(util::pixel_relative(value, axis, constraints, screen), element_constraints.get_x(), self.get_width())
                                             }
                                             ElementRef::Parent => {
-                                                util::from_left(util::pixel_relative(value, axis, element_constraints), element_constraints.get_x(), self.get_width())
+                                                util::from_left(util::pixel_relative(value, axis, element_constraints, screen), element_constraints.get_x(), self.get_width())
                                             }
                                             ElementRef::Screen => {
                                                 util::from_left(util::screen_pixel_relative(value, axis, screen), element_constraints.get_x(), self.get_width())
@@ -1183,7 +1198,7 @@ impl Constraints {
                                             ElementRef::Other(id) => {
                                                 let mut element2 = system.get_entity_mut(id.clone());
                                                 let element_constraints2 = element2.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", id).as_str());
-                                                util::from_left(util::pixel_relative(value, axis, element_constraints2), element_constraints.get_x(), self.get_width())
+                                                util::from_left(util::pixel_relative(value, axis, element_constraints2, screen), element_constraints.get_x(), self.get_width())
                                             }
                                         }
                                     }
@@ -1191,12 +1206,12 @@ impl Constraints {
                                         match relative {
                                             ElementRef::This => {
                                                 let constraints = MutRef::new(self as *mut Constraints);
-                                                util::from_left(util::pixel_relative(value, axis, constraints), 0.0, self.get_width())
+                                                util::from_left(util::pixel_relative(value, axis, constraints, screen), 0.0, self.get_width())
                                             }
                                             ElementRef::Parent => {
                                                 let mut element = system.get_entity_mut(parent.clone().unwrap());
                                                 let element_constraints = element.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", parent.clone().unwrap()).as_str());
-                                                util::from_left(util::pixel_relative(value, axis, element_constraints), 0.0, self.get_width())
+                                                util::from_left(util::pixel_relative(value, axis, element_constraints, screen), 0.0, self.get_width())
                                             }
                                             ElementRef::Screen => {
                                                 util::from_left(util::screen_pixel_relative(value, axis, screen), 0.0, self.get_width())
@@ -1204,7 +1219,7 @@ impl Constraints {
                                             ElementRef::Other(id) => {
                                                 let mut element = system.get_entity_mut(id.clone());
                                                 let element_constraints = element.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", id).as_str());
-                                                util::from_left(util::pixel_relative(value, axis, element_constraints), 0.0, self.get_width())
+                                                util::from_left(util::pixel_relative(value, axis, element_constraints, screen), 0.0, self.get_width())
                                             }
                                         }
                                     }
@@ -1214,10 +1229,10 @@ impl Constraints {
                                         match relative {
                                             ElementRef::This => {
                                                 let constraints = MutRef::new(self as *mut Constraints);
-                                                util::from_left(util::pixel_relative(value, axis, constraints), element_constraints.get_x(), self.get_width())
+                                                util::from_left(util::pixel_relative(value, axis, constraints, screen), element_constraints.get_x(), self.get_width())
                                             }
                                             ElementRef::Parent => {
-                                                util::from_left(util::pixel_relative(value, axis, element_constraints), element_constraints.get_x(), self.get_width())
+                                                util::from_left(util::pixel_relative(value, axis, element_constraints, screen), element_constraints.get_x(), self.get_width())
                                             }
                                             ElementRef::Screen => {
                                                 util::from_left(util::screen_pixel_relative(value, axis, screen), element_constraints.get_x(), self.get_width())
@@ -1225,7 +1240,7 @@ impl Constraints {
                                             ElementRef::Other(id) => {
                                                 let mut element2 = system.get_entity_mut(id.clone());
                                                 let element_constraints2 = element2.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", id).as_str());
-                                                util::from_left(util::pixel_relative(value, axis, element_constraints2), element_constraints.get_x(), self.get_width())
+                                                util::from_left(util::pixel_relative(value, axis, element_constraints2, screen), element_constraints.get_x(), self.get_width())
                                             }
                                         }
                                     }
@@ -1243,10 +1258,10 @@ impl Constraints {
                                         match relative {
                                             ElementRef::This => {
                                                 let constraints = MutRef::new(self as *mut Constraints);
-                                                util::from_right(util::pixel_relative(value, axis, constraints), element_constraints.get_x(), element_constraints.get_width())
+                                                util::from_right(util::pixel_relative(value, axis, constraints, screen), element_constraints.get_x(), element_constraints.get_width())
                                             }
                                             ElementRef::Parent => {
-                                                util::from_right(util::pixel_relative(value, axis, element_constraints), element_constraints.get_x(), element_constraints.get_width())
+                                                util::from_right(util::pixel_relative(value, axis, element_constraints, screen), element_constraints.get_x(), element_constraints.get_width())
                                             }
                                             ElementRef::Screen => {
                                                 util::from_right(util::screen_pixel_relative(value, axis, screen), element_constraints.get_x(), element_constraints.get_width())
@@ -1254,7 +1269,7 @@ impl Constraints {
                                             ElementRef::Other(id) => {
                                                 let mut element2 = system.get_entity_mut(id.clone());
                                                 let element_constraints2 = element2.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", id).as_str());
-                                                util::from_right(util::pixel_relative(value, axis, element_constraints2), element_constraints.get_x(), element_constraints.get_width())
+                                                util::from_right(util::pixel_relative(value, axis, element_constraints2, screen), element_constraints.get_x(), element_constraints.get_width())
                                             }
                                         }
                                     }
@@ -1262,12 +1277,12 @@ impl Constraints {
                                         match relative {
                                             ElementRef::This => {
                                                 let constraints = MutRef::new(self as *mut Constraints);
-                                                util::from_right(util::pixel_relative(value, axis, constraints), 0.0, screen.width as f32)
+                                                util::from_right(util::pixel_relative(value, axis, constraints, screen), 0.0, screen.width as f32)
                                             }
                                             ElementRef::Parent => {
                                                 let mut element = system.get_entity_mut(parent.clone().unwrap());
                                                 let element_constraints = element.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", parent.clone().unwrap()).as_str());
-                                                util::from_right(util::pixel_relative(value, axis, element_constraints), 0.0, screen.width as f32)
+                                                util::from_right(util::pixel_relative(value, axis, element_constraints, screen), 0.0, screen.width as f32)
                                             }
                                             ElementRef::Screen => {
                                                 util::from_right(util::screen_pixel_relative(value, axis, screen), 0.0, screen.width as f32)
@@ -1275,7 +1290,7 @@ impl Constraints {
                                             ElementRef::Other(id) => {
                                                 let mut element = system.get_entity_mut(id.clone());
                                                 let element_constraints = element.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", id).as_str());
-                                                util::from_right(util::pixel_relative(value, axis, element_constraints), 0.0, screen.width as f32)
+                                                util::from_right(util::pixel_relative(value, axis, element_constraints, screen), 0.0, screen.width as f32)
                                             }
                                         }
                                     }
@@ -1285,10 +1300,10 @@ impl Constraints {
                                         match relative {
                                             ElementRef::This => {
                                                 let constraints = MutRef::new(self as *mut Constraints);
-                                                util::from_right(util::pixel_relative(value, axis, constraints), element_constraints.get_x(), element_constraints.get_width())
+                                                util::from_right(util::pixel_relative(value, axis, constraints, screen), element_constraints.get_x(), element_constraints.get_width())
                                             }
                                             ElementRef::Parent => {
-                                                util::from_right(util::pixel_relative(value, axis, element_constraints), element_constraints.get_x(), element_constraints.get_width())
+                                                util::from_right(util::pixel_relative(value, axis, element_constraints, screen), element_constraints.get_x(), element_constraints.get_width())
                                             }
                                             ElementRef::Screen => {
                                                 util::from_right(util::screen_pixel_relative(value, axis, screen), element_constraints.get_x(), element_constraints.get_width())
@@ -1296,7 +1311,7 @@ impl Constraints {
                                             ElementRef::Other(id) => {
                                                 let mut element2 = system.get_entity_mut(id.clone());
                                                 let element_constraints2 = element2.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", id).as_str());
-                                                util::from_right(util::pixel_relative(value, axis, element_constraints2), element_constraints.get_x(), element_constraints.get_width())
+                                                util::from_right(util::pixel_relative(value, axis, element_constraints2, screen), element_constraints.get_x(), element_constraints.get_width())
                                             }
                                         }
                                     }
@@ -1314,10 +1329,10 @@ impl Constraints {
                                         match relative {
                                             ElementRef::This => {
                                                 let constraints = MutRef::new(self as *mut Constraints);
-                                                util::in_left(util::pixel_relative(value, axis, constraints), element_constraints.get_x())
+                                                util::in_left(util::pixel_relative(value, axis, constraints, screen), element_constraints.get_x())
                                             }
                                             ElementRef::Parent => {
-                                                util::in_left(util::pixel_relative(value, axis, element_constraints), element_constraints.get_x())
+                                                util::in_left(util::pixel_relative(value, axis, element_constraints, screen), element_constraints.get_x())
                                             }
                                             ElementRef::Screen => {
                                                 util::in_left(util::screen_pixel_relative(value, axis, screen), element_constraints.get_x())
@@ -1325,7 +1340,7 @@ impl Constraints {
                                             ElementRef::Other(id) => {
                                                 let mut element2 = system.get_entity_mut(id.clone());
                                                 let element_constraints2 = element2.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", id).as_str());
-                                                util::in_left(util::pixel_relative(value, axis, element_constraints2), element_constraints.get_x())
+                                                util::in_left(util::pixel_relative(value, axis, element_constraints2, screen), element_constraints.get_x())
                                             }
                                         }
                                     }
@@ -1333,12 +1348,12 @@ impl Constraints {
                                         match relative {
                                             ElementRef::This => {
                                                 let constraints = MutRef::new(self as *mut Constraints);
-                                                util::in_left(util::pixel_relative(value, axis, constraints), 0.0)
+                                                util::in_left(util::pixel_relative(value, axis, constraints, screen), 0.0)
                                             }
                                             ElementRef::Parent => {
                                                 let mut element = system.get_entity_mut(parent.clone().unwrap());
                                                 let element_constraints = element.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", parent.clone().unwrap()).as_str());
-                                                util::in_left(util::pixel_relative(value, axis, element_constraints), 0.0)
+                                                util::in_left(util::pixel_relative(value, axis, element_constraints, screen), 0.0)
                                             }
                                             ElementRef::Screen => {
                                                 util::in_left(util::screen_pixel_relative(value, axis, screen), 0.0)
@@ -1346,7 +1361,7 @@ impl Constraints {
                                             ElementRef::Other(id) => {
                                                 let mut element = system.get_entity_mut(id.clone());
                                                 let element_constraints = element.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", id).as_str());
-                                                util::in_left(util::pixel_relative(value, axis, element_constraints), 0.0)
+                                                util::in_left(util::pixel_relative(value, axis, element_constraints, screen), 0.0)
                                             }
                                         }
                                     }
@@ -1356,12 +1371,12 @@ impl Constraints {
                                         match relative {
                                             ElementRef::This => {
                                                 let constraints = MutRef::new(self as *mut Constraints);
-                                                util::in_left(util::pixel_relative(value, axis, constraints), element_constraints.get_x())
+                                                util::in_left(util::pixel_relative(value, axis, constraints, screen), element_constraints.get_x())
                                             }
                                             ElementRef::Parent => {
                                                 let mut element2 = system.get_entity_mut(parent.clone().unwrap());
                                                 let element_constraints2 = element2.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", parent.clone().unwrap()).as_str());
-                                                util::in_left(util::pixel_relative(value, axis, element_constraints2), element_constraints.get_x())
+                                                util::in_left(util::pixel_relative(value, axis, element_constraints2, screen), element_constraints.get_x())
                                             }
                                             ElementRef::Screen => {
                                                 util::in_left(util::screen_pixel_relative(value, axis, screen), element_constraints.get_x())
@@ -1369,7 +1384,7 @@ impl Constraints {
                                             ElementRef::Other(id) => {
                                                 let mut element2 = system.get_entity_mut(id.clone());
                                                 let element_constraints2 = element2.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", id).as_str());
-                                                util::in_left(util::pixel_relative(value, axis, element_constraints2), element_constraints.get_x())
+                                                util::in_left(util::pixel_relative(value, axis, element_constraints2, screen), element_constraints.get_x())
                                             }
                                         }
                                     }
@@ -1387,10 +1402,10 @@ impl Constraints {
                                         match relative {
                                             ElementRef::This => {
                                                 let constraints = MutRef::new(self as *mut Constraints);
-                                                util::in_right(util::pixel_relative(value, axis, constraints), element_constraints.get_x(), element_constraints.get_width(), self.get_width())
+                                                util::in_right(util::pixel_relative(value, axis, constraints, screen), element_constraints.get_x(), element_constraints.get_width(), self.get_width())
                                             }
                                             ElementRef::Parent => {
-                                                util::in_right(util::pixel_relative(value, axis, element_constraints), element_constraints.get_x(), element_constraints.get_width(), self.get_width())
+                                                util::in_right(util::pixel_relative(value, axis, element_constraints, screen), element_constraints.get_x(), element_constraints.get_width(), self.get_width())
                                             }
                                             ElementRef::Screen => {
                                                 util::in_right(util::screen_pixel_relative(value, axis, screen), element_constraints.get_x(), element_constraints.get_width(), self.get_width())
@@ -1398,7 +1413,7 @@ impl Constraints {
                                             ElementRef::Other(id) => {
                                                 let mut element2 = system.get_entity_mut(id.clone());
                                                 let element_constraints2 = element2.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", id).as_str());
-                                                util::in_right(util::pixel_relative(value, axis, element_constraints2), element_constraints.get_x(), element_constraints.get_width(), self.get_width())
+                                                util::in_right(util::pixel_relative(value, axis, element_constraints2, screen), element_constraints.get_x(), element_constraints.get_width(), self.get_width())
                                             }
                                         }
                                     }
@@ -1406,12 +1421,12 @@ impl Constraints {
                                         match relative {
                                             ElementRef::This => {
                                                 let constraints = MutRef::new(self as *mut Constraints);
-                                                util::in_right(util::pixel_relative(value, axis, constraints), 0.0, screen.width as f32, self.get_width())
+                                                util::in_right(util::pixel_relative(value, axis, constraints, screen), 0.0, screen.width as f32, self.get_width())
                                             }
                                             ElementRef::Parent => {
                                                 let mut element = system.get_entity_mut(parent.clone().unwrap());
                                                 let element_constraints = element.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", parent.clone().unwrap()).as_str());
-                                                util::in_right(util::pixel_relative(value, axis, element_constraints), 0.0, screen.width as f32, self.get_width())
+                                                util::in_right(util::pixel_relative(value, axis, element_constraints, screen), 0.0, screen.width as f32, self.get_width())
                                             }
                                             ElementRef::Screen => {
                                                 util::in_right(util::screen_pixel_relative(value, axis, screen), 0.0, screen.width as f32, self.get_width())
@@ -1419,7 +1434,7 @@ impl Constraints {
                                             ElementRef::Other(id) => {
                                                 let mut element = system.get_entity_mut(id.clone());
                                                 let element_constraints = element.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", id).as_str());
-                                                util::in_right(util::pixel_relative(value, axis, element_constraints), 0.0, screen.width as f32, self.get_width())
+                                                util::in_right(util::pixel_relative(value, axis, element_constraints, screen), 0.0, screen.width as f32, self.get_width())
                                             }
                                         }
                                     }
@@ -1429,12 +1444,12 @@ impl Constraints {
                                         match relative {
                                             ElementRef::This => {
                                                 let constraints = MutRef::new(self as *mut Constraints);
-                                                util::in_right(util::pixel_relative(value, axis, constraints), element_constraints.get_x(), element_constraints.get_width(), self.get_width())
+                                                util::in_right(util::pixel_relative(value, axis, constraints, screen), element_constraints.get_x(), element_constraints.get_width(), self.get_width())
                                             }
                                             ElementRef::Parent => {
                                                 let mut element2 = system.get_entity_mut(parent.clone().unwrap());
                                                 let element_constraints2 = element2.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", parent.clone().unwrap()).as_str());
-                                                util::in_right(util::pixel_relative(value, axis, element_constraints2), element_constraints.get_x(), element_constraints.get_width(), self.get_width())
+                                                util::in_right(util::pixel_relative(value, axis, element_constraints2, screen), element_constraints.get_x(), element_constraints.get_width(), self.get_width())
                                             }
                                             ElementRef::Screen => {
                                                 util::in_right(util::screen_pixel_relative(value, axis, screen), element_constraints.get_x(), element_constraints.get_width(), self.get_width())
@@ -1442,7 +1457,7 @@ impl Constraints {
                                             ElementRef::Other(id) => {
                                                 let mut element2 = system.get_entity_mut(id.clone());
                                                 let element_constraints2 = element2.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", id).as_str());
-                                                util::in_right(util::pixel_relative(value, axis, element_constraints2), element_constraints.get_x(), element_constraints.get_width(), self.get_width())
+                                                util::in_right(util::pixel_relative(value, axis, element_constraints2, screen), element_constraints.get_x(), element_constraints.get_width(), self.get_width())
                                             }
                                         }
                                     }
@@ -1450,6 +1465,9 @@ impl Constraints {
                             },
                         }
                     } else {
+                        // Pixel values are logical, convert them to framebuffer pixels
+                        let value = util::screen_pixel(value, Axis::X, screen);
+
                         match side {
                             SideX::FromLeft => {
                                 match from {
@@ -1546,10 +1564,10 @@ impl Constraints {
                                 match relative {
                                     ElementRef::This => {
                                         let constraints = MutRef::new(self as *mut Constraints);
-                                        util::centered(util::pixel_relative(value, axis, constraints), element_constraints.get_x(), element_constraints.get_width(), self.get_width())
+                                        util::centered(util::pixel_relative(value, axis, constraints, screen), element_constraints.get_x(), element_constraints.get_width(), self.get_width())
                                     }
                                     ElementRef::Parent => {
-                                        util::centered(util::pixel_relative(value, axis, element_constraints), element_constraints.get_x(), element_constraints.get_width(), self.get_width())
+                                        util::centered(util::pixel_relative(value, axis, element_constraints, screen), element_constraints.get_x(), element_constraints.get_width(), self.get_width())
                                     }
                                     ElementRef::Screen => {
                                         util::centered(util::screen_pixel_relative(value, axis, screen), element_constraints.get_x(), element_constraints.get_width(), self.get_width())
@@ -1557,7 +1575,7 @@ impl Constraints {
                                     ElementRef::Other(id) => {
                                         let mut element2 = system.get_entity_mut(id.clone());
                                         let element_constraints2 = element2.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", id.clone()).as_str());
-                                        util::centered(util::pixel_relative(value, axis, element_constraints2), element_constraints.get_x(), element_constraints.get_width(), self.get_width())
+                                        util::centered(util::pixel_relative(value, axis, element_constraints2, screen), element_constraints.get_x(), element_constraints.get_width(), self.get_width())
                                     }
                                 }
                             }
@@ -1565,12 +1583,12 @@ impl Constraints {
                                 match relative {
                                     ElementRef::This => {
                                         let constraints = MutRef::new(self as *mut Constraints);
-                                        util::centered(util::pixel_relative(value, axis, constraints), 0.0, screen.width as f32, self.get_width())
+                                        util::centered(util::pixel_relative(value, axis, constraints, screen), 0.0, screen.width as f32, self.get_width())
                                     }
                                     ElementRef::Parent => {
                                         let mut element = system.get_entity_mut(parent.clone().unwrap());
                                         let element_constraints = element.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", parent.clone().unwrap()).as_str());
-                                        util::centered(util::pixel_relative(value, axis, element_constraints), 0.0, screen.width as f32, self.get_width())
+                                        util::centered(util::pixel_relative(value, axis, element_constraints, screen), 0.0, screen.width as f32, self.get_width())
                                     }
                                     ElementRef::Screen => {
                                         util::centered(util::screen_pixel_relative(value, axis, screen), 0.0, screen.width as f32, self.get_width())
@@ -1578,7 +1596,7 @@ impl Constraints {
                                     ElementRef::Other(id) => {
                                         let mut element = system.get_entity_mut(id.clone());
                                         let element_constraints = element.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", id.clone()).as_str());
-                                        util::centered(util::pixel_relative(value, axis, element_constraints), 0.0, screen.width as f32, self.get_width())
+                                        util::centered(util::pixel_relative(value, axis, element_constraints, screen), 0.0, screen.width as f32, self.get_width())
                                     }
                                 }
                             }
@@ -1589,12 +1607,12 @@ impl Constraints {
                                 match relative {
                                     ElementRef::This => {
                                         let constraints = MutRef::new(self as *mut Constraints);
-                                        util::centered(util::pixel_relative(value, axis, constraints), element_constraints.get_x(), element_constraints.get_width(), self.get_width())
+                                        util::centered(util::pixel_relative(value, axis, constraints, screen), element_constraints.get_x(), element_constraints.get_width(), self.get_width())
                                     }
                                     ElementRef::Parent => {
                                         let mut element2 = system.get_entity_mut(parent.clone().unwrap());
                                         let element_constraints2 = element2.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", parent.clone().unwrap()).as_str());
-                                        util::centered(util::pixel_relative(value, axis, element_constraints2), element_constraints.get_x(), element_constraints.get_width(), self.get_width())
+                                        util::centered(util::pixel_relative(value, axis, element_constraints2, screen), element_constraints.get_x(), element_constraints.get_width(), self.get_width())
                                     }
                                     ElementRef::Screen => {
                                         util::centered(util::screen_pixel_relative(value, axis, screen), element_constraints.get_x(), element_constraints.get_width(), self.get_width())
@@ -1602,12 +1620,15 @@ impl Constraints {
                                     ElementRef::Other(id) => {
                                         let mut element2 = system.get_entity_mut(id.clone());
                                         let element_constraints2 = element2.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", id.clone()).as_str());
-                                        util::centered(util::pixel_relative(value, axis, element_constraints2), element_constraints.get_x(), element_constraints.get_width(), self.get_width())
+                                        util::centered(util::pixel_relative(value, axis, element_constraints2, screen), element_constraints.get_x(), element_constraints.get_width(), self.get_width())
                                     }
                                 }
                             }
                         }
                     } else {
+                        // Pixel values are logical, convert them to framebuffer pixels
+                        let value = util::screen_pixel(value, Axis::X, screen);
+
                         match from {
                             ElementRef::This => {
                                 warn!("Pepper/Constraints", "Recursive x constraint for '{}'", self.parent.clone().unwrap());
@@ -2032,10 +2053,10 @@ impl Constraints {
                                         match relative {
                                             ElementRef::This => {
                                                 let constraints = MutRef::new(self as *mut Constraints);
-                                                util::from_left(util::pixel_relative(value, axis, constraints), element_constraints.get_y(), self.get_height())
+                                                util::from_left(util::pixel_relative(value, axis, constraints, screen), element_constraints.get_y(), self.get_height())
                                             }
                                             ElementRef::Parent => {
-                                                util::from_left(util::pixel_relative(value, axis, element_constraints), element_constraints.get_y(), self.get_height())
+                                                util::from_left(util::pixel_relative(value, axis, element_constraints, screen), element_constraints.get_y(), self.get_height())
                                             }
                                             ElementRef::Screen => {
                                                 util::from_left(util::screen_pixel_relative(value, axis, screen), element_constraints.get_y(), self.get_height())
@@ -2043,7 +2064,7 @@ impl Constraints {
                                             ElementRef::Other(id) => {
                                                 let mut element2 = system.get_entity_mut(id.clone());
                                                 let element_constraints2 = element2.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", id).as_str());
-                                                util::from_left(util::pixel_relative(value, axis, element_constraints2), element_constraints.get_y(), self.get_height())
+                                                util::from_left(util::pixel_relative(value, axis, element_constraints2, screen), element_constraints.get_y(), self.get_height())
                                             }
                                         }
                                     }
@@ -2051,12 +2072,12 @@ impl Constraints {
                                         match relative {
                                             ElementRef::This => {
                                                 let constraints = MutRef::new(self as *mut Constraints);
-                                                util::from_left(util::pixel_relative(value, axis, constraints), 0.0, self.get_height())
+                                                util::from_left(util::pixel_relative(value, axis, constraints, screen), 0.0, self.get_height())
                                             }
                                             ElementRef::Parent => {
                                                 let mut element = system.get_entity_mut(parent.clone().unwrap());
                                                 let element_constraints = element.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", parent.clone().unwrap()).as_str());
-                                                util::from_left(util::pixel_relative(value, axis, element_constraints), 0.0, self.get_height())
+                                                util::from_left(util::pixel_relative(value, axis, element_constraints, screen), 0.0, self.get_height())
                                             }
                                             ElementRef::Screen => {
                                                 util::from_left(util::screen_pixel_relative(value, axis, screen), 0.0, self.get_height())
@@ -2064,7 +2085,7 @@ impl Constraints {
                                             ElementRef::Other(id) => {
                                                 let mut element = system.get_entity_mut(id.clone());
                                                 let element_constraints = element.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", id).as_str());
-                                                util::from_left(util::pixel_relative(value, axis, element_constraints), 0.0, self.get_height())
+                                                util::from_left(util::pixel_relative(value, axis, element_constraints, screen), 0.0, self.get_height())
                                             }
                                         }
                                     }
@@ -2074,10 +2095,10 @@ impl Constraints {
                                         match relative {
                                             ElementRef::This => {
                                                 let constraints = MutRef::new(self as *mut Constraints);
-                                                util::from_left(util::pixel_relative(value, axis, constraints), element_constraints.get_y(), self.get_height())
+                                                util::from_left(util::pixel_relative(value, axis, constraints, screen), element_constraints.get_y(), self.get_height())
                                             }
                                             ElementRef::Parent => {
-                                                util::from_left(util::pixel_relative(value, axis, element_constraints), element_constraints.get_y(), self.get_height())
+                                                util::from_left(util::pixel_relative(value, axis, element_constraints, screen), element_constraints.get_y(), self.get_height())
                                             }
                                             ElementRef::Screen => {
                                                 util::from_left(util::screen_pixel_relative(value, axis, screen), element_constraints.get_y(), self.get_height())
@@ -2085,7 +2106,7 @@ impl Constraints {
                                             ElementRef::Other(id) => {
                                                 let mut element2 = system.get_entity_mut(id.clone());
                                                 let element_constraints2 = element2.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", id).as_str());
-                                                util::from_left(util::pixel_relative(value, axis, element_constraints2), element_constraints.get_y(), self.get_height())
+                                                util::from_left(util::pixel_relative(value, axis, element_constraints2, screen), element_constraints.get_y(), self.get_height())
                                             }
                                         }
                                     }
@@ -2103,10 +2124,10 @@ impl Constraints {
                                         match relative {
                                             ElementRef::This => {
                                                 let constraints = MutRef::new(self as *mut Constraints);
-                                                util::from_right(util::pixel_relative(value, axis, constraints), element_constraints.get_y(), element_constraints.get_height())
+                                                util::from_right(util::pixel_relative(value, axis, constraints, screen), element_constraints.get_y(), element_constraints.get_height())
                                             }
                                             ElementRef::Parent => {
-                                                util::from_right(util::pixel_relative(value, axis, element_constraints), element_constraints.get_y(), element_constraints.get_height())
+                                                util::from_right(util::pixel_relative(value, axis, element_constraints, screen), element_constraints.get_y(), element_constraints.get_height())
                                             }
                                             ElementRef::Screen => {
                                                 util::from_right(util::screen_pixel_relative(value, axis, screen), element_constraints.get_y(), element_constraints.get_height())
@@ -2114,7 +2135,7 @@ impl Constraints {
                                             ElementRef::Other(id) => {
                                                 let mut element2 = system.get_entity_mut(id.clone());
                                                 let element_constraints2 = element2.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", id).as_str());
-                                                util::from_right(util::pixel_relative(value, axis, element_constraints2), element_constraints.get_y(), element_constraints.get_height())
+                                                util::from_right(util::pixel_relative(value, axis, element_constraints2, screen), element_constraints.get_y(), element_constraints.get_height())
                                             }
                                         }
                                     }
@@ -2122,12 +2143,12 @@ impl Constraints {
                                         match relative {
                                             ElementRef::This => {
                                                 let constraints = MutRef::new(self as *mut Constraints);
-                                                util::from_right(util::pixel_relative(value, axis, constraints), 0.0, screen.height as f32)
+                                                util::from_right(util::pixel_relative(value, axis, constraints, screen), 0.0, screen.height as f32)
                                             }
                                             ElementRef::Parent => {
                                                 let mut element = system.get_entity_mut(parent.clone().unwrap());
                                                 let element_constraints = element.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", parent.clone().unwrap()).as_str());
-                                                util::from_right(util::pixel_relative(value, axis, element_constraints), 0.0, screen.height as f32)
+                                                util::from_right(util::pixel_relative(value, axis, element_constraints, screen), 0.0, screen.height as f32)
                                             }
                                             ElementRef::Screen => {
                                                 util::from_right(util::screen_pixel_relative(value, axis, screen), 0.0, screen.height as f32)
@@ -2135,7 +2156,7 @@ impl Constraints {
                                             ElementRef::Other(id) => {
                                                 let mut element = system.get_entity_mut(id.clone());
                                                 let element_constraints = element.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", id).as_str());
-                                                util::from_right(util::pixel_relative(value, axis, element_constraints), 0.0, screen.height as f32)
+                                                util::from_right(util::pixel_relative(value, axis, element_constraints, screen), 0.0, screen.height as f32)
                                             }
                                         }
                                     }
@@ -2145,10 +2166,10 @@ impl Constraints {
                                         match relative {
                                             ElementRef::This => {
                                                 let constraints = MutRef::new(self as *mut Constraints);
-                                                util::from_right(util::pixel_relative(value, axis, constraints), element_constraints.get_y(), element_constraints.get_height())
+                                                util::from_right(util::pixel_relative(value, axis, constraints, screen), element_constraints.get_y(), element_constraints.get_height())
                                             }
                                             ElementRef::Parent => {
-                                                util::from_right(util::pixel_relative(value, axis, element_constraints), element_constraints.get_y(), element_constraints.get_height())
+                                                util::from_right(util::pixel_relative(value, axis, element_constraints, screen), element_constraints.get_y(), element_constraints.get_height())
                                             }
                                             ElementRef::Screen => {
                                                 util::from_right(util::screen_pixel_relative(value, axis, screen), element_constraints.get_y(), element_constraints.get_height())
@@ -2156,7 +2177,7 @@ impl Constraints {
                                             ElementRef::Other(id) => {
                                                 let mut element2 = system.get_entity_mut(id.clone());
                                                 let element_constraints2 = element2.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", id).as_str());
-                                                util::from_right(util::pixel_relative(value, axis, element_constraints2), element_constraints.get_y(), element_constraints.get_height())
+                                                util::from_right(util::pixel_relative(value, axis, element_constraints2, screen), element_constraints.get_y(), element_constraints.get_height())
                                             }
                                         }
                                     }
@@ -2174,10 +2195,10 @@ impl Constraints {
                                         match relative {
                                             ElementRef::This => {
                                                 let constraints = MutRef::new(self as *mut Constraints);
-                                                util::in_left(util::pixel_relative(value, axis, constraints), element_constraints.get_y())
+                                                util::in_left(util::pixel_relative(value, axis, constraints, screen), element_constraints.get_y())
                                             }
                                             ElementRef::Parent => {
-                                                util::in_left(util::pixel_relative(value, axis, element_constraints), element_constraints.get_y())
+                                                util::in_left(util::pixel_relative(value, axis, element_constraints, screen), element_constraints.get_y())
                                             }
                                             ElementRef::Screen => {
                                                 util::in_left(util::screen_pixel_relative(value, axis, screen), element_constraints.get_y())
@@ -2185,7 +2206,7 @@ impl Constraints {
                                             ElementRef::Other(id) => {
                                                 let mut element2 = system.get_entity_mut(id.clone());
                                                 let element_constraints2 = element2.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", id).as_str());
-                                                util::in_left(util::pixel_relative(value, axis, element_constraints2), element_constraints.get_y())
+                                                util::in_left(util::pixel_relative(value, axis, element_constraints2, screen), element_constraints.get_y())
                                             }
                                         }
                                     }
@@ -2193,12 +2214,12 @@ impl Constraints {
                                         match relative {
                                             ElementRef::This => {
                                                 let constraints = MutRef::new(self as *mut Constraints);
-                                                util::in_left(util::pixel_relative(value, axis, constraints), 0.0)
+                                                util::in_left(util::pixel_relative(value, axis, constraints, screen), 0.0)
                                             }
                                             ElementRef::Parent => {
                                                 let mut element = system.get_entity_mut(parent.clone().unwrap());
                                                 let element_constraints = element.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", parent.clone().unwrap()).as_str());
-                                                util::in_left(util::pixel_relative(value, axis, element_constraints), 0.0)
+                                                util::in_left(util::pixel_relative(value, axis, element_constraints, screen), 0.0)
                                             }
                                             ElementRef::Screen => {
                                                 util::in_left(util::screen_pixel_relative(value, axis, screen), 0.0)
@@ -2206,7 +2227,7 @@ impl Constraints {
                                             ElementRef::Other(id) => {
                                                 let mut element = system.get_entity_mut(id.clone());
                                                 let element_constraints = element.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", id).as_str());
-                                                util::in_left(util::pixel_relative(value, axis, element_constraints), 0.0)
+                                                util::in_left(util::pixel_relative(value, axis, element_constraints, screen), 0.0)
                                             }
                                         }
                                     }
@@ -2216,12 +2237,12 @@ impl Constraints {
                                         match relative {
                                             ElementRef::This => {
                                                 let constraints = MutRef::new(self as *mut Constraints);
-                                                util::in_left(util::pixel_relative(value, axis, constraints), element_constraints.get_y())
+                                                util::in_left(util::pixel_relative(value, axis, constraints, screen), element_constraints.get_y())
                                             }
                                             ElementRef::Parent => {
                                                 let mut element2 = system.get_entity_mut(parent.clone().unwrap());
                                                 let element_constraints2 = element2.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", parent.clone().unwrap()).as_str());
-                                                util::in_left(util::pixel_relative(value, axis, element_constraints2), element_constraints.get_y())
+                                                util::in_left(util::pixel_relative(value, axis, element_constraints2, screen), element_constraints.get_y())
                                             }
                                             ElementRef::Screen => {
                                                 util::in_left(util::screen_pixel_relative(value, axis, screen), element_constraints.get_y())
@@ -2229,7 +2250,7 @@ impl Constraints {
                                             ElementRef::Other(id) => {
                                                 let mut element2 = system.get_entity_mut(id.clone());
                                                 let element_constraints2 = element2.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", id).as_str());
-                                                util::in_left(util::pixel_relative(value, axis, element_constraints2), element_constraints.get_y())
+                                                util::in_left(util::pixel_relative(value, axis, element_constraints2, screen), element_constraints.get_y())
                                             }
                                         }
                                     }
@@ -2247,10 +2268,10 @@ impl Constraints {
                                         match relative {
                                             ElementRef::This => {
                                                 let constraints = MutRef::new(self as *mut Constraints);
-                                                util::in_right(util::pixel_relative(value, axis, constraints), element_constraints.get_y(), element_constraints.get_height(), self.get_height())
+                                                util::in_right(util::pixel_relative(value, axis, constraints, screen), element_constraints.get_y(), element_constraints.get_height(), self.get_height())
                                             }
                                             ElementRef::Parent => {
-                                                util::in_right(util::pixel_relative(value, axis, element_constraints), element_constraints.get_y(), element_constraints.get_height(), self.get_height())
+                                                util::in_right(util::pixel_relative(value, axis, element_constraints, screen), element_constraints.get_y(), element_constraints.get_height(), self.get_height())
                                             }
                                             ElementRef::Screen => {
                                                 util::in_right(util::screen_pixel_relative(value, axis, screen), element_constraints.get_y(), element_constraints.get_height(), self.get_height())
@@ -2258,7 +2279,7 @@ impl Constraints {
                                             ElementRef::Other(id) => {
                                                 let mut element2 = system.get_entity_mut(id.clone());
                                                 let element_constraints2 = element2.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", id).as_str());
-                                                util::in_right(util::pixel_relative(value, axis, element_constraints2), element_constraints.get_y(), element_constraints.get_height(), self.get_height())
+                                                util::in_right(util::pixel_relative(value, axis, element_constraints2, screen), element_constraints.get_y(), element_constraints.get_height(), self.get_height())
                                             }
                                         }
                                     }
@@ -2266,12 +2287,12 @@ impl Constraints {
                                         match relative {
                                             ElementRef::This => {
                                                 let constraints = MutRef::new(self as *mut Constraints);
-                                                util::in_right(util::pixel_relative(value, axis, constraints), 0.0, screen.height as f32, self.get_height())
+                                                util::in_right(util::pixel_relative(value, axis, constraints, screen), 0.0, screen.height as f32, self.get_height())
                                             }
                                             ElementRef::Parent => {
                                                 let mut element = system.get_entity_mut(parent.clone().unwrap());
                                                 let element_constraints = element.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", parent.clone().unwrap()).as_str());
-                                                util::in_right(util::pixel_relative(value, axis, element_constraints), 0.0, screen.height as f32, self.get_height())
+                                                util::in_right(util::pixel_relative(value, axis, element_constraints, screen), 0.0, screen.height as f32, self.get_height())
                                             }
                                             ElementRef::Screen => {
                                                 util::in_right(util::screen_pixel_relative(value, axis, screen), 0.0, screen.height as f32, self.get_height())
@@ -2279,7 +2300,7 @@ impl Constraints {
                                             ElementRef::Other(id) => {
                                                 let mut element = system.get_entity_mut(id.clone());
                                                 let element_constraints = element.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", id).as_str());
-                                                util::in_right(util::pixel_relative(value, axis, element_constraints), 0.0, screen.height as f32, self.get_height())
+                                                util::in_right(util::pixel_relative(value, axis, element_constraints, screen), 0.0, screen.height as f32, self.get_height())
                                             }
                                         }
                                     }
@@ -2289,12 +2310,12 @@ impl Constraints {
                                         match relative {
                                             ElementRef::This => {
                                                 let constraints = MutRef::new(self as *mut Constraints);
-                                                util::in_right(util::pixel_relative(value, axis, constraints), element_constraints.get_y(), element_constraints.get_height(), self.get_height())
+                                                util::in_right(util::pixel_relative(value, axis, constraints, screen), element_constraints.get_y(), element_constraints.get_height(), self.get_height())
                                             }
                                             ElementRef::Parent => {
                                                 let mut element2 = system.get_entity_mut(parent.clone().unwrap());
                                                 let element_constraints2 = element2.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", parent.clone().unwrap()).as_str());
-                                                util::in_right(util::pixel_relative(value, axis, element_constraints2), element_constraints.get_y(), element_constraints.get_height(), self.get_height())
+                                                util::in_right(util::pixel_relative(value, axis, element_constraints2, screen), element_constraints.get_y(), element_constraints.get_height(), self.get_height())
                                             }
                                             ElementRef::Screen => {
                                                 util::in_right(util::screen_pixel_relative(value, axis, screen), element_constraints.get_y(), element_constraints.get_height(), self.get_height())
@@ -2302,7 +2323,7 @@ impl Constraints {
                                             ElementRef::Other(id) => {
                                                 let mut element2 = system.get_entity_mut(id.clone());
                                                 let element_constraints2 = element2.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", id).as_str());
-                                                util::in_right(util::pixel_relative(value, axis, element_constraints2), element_constraints.get_y(), element_constraints.get_height(), self.get_height())
+                                                util::in_right(util::pixel_relative(value, axis, element_constraints2, screen), element_constraints.get_y(), element_constraints.get_height(), self.get_height())
                                             }
                                         }
                                     }
@@ -2310,6 +2331,9 @@ impl Constraints {
                             },
                         }
                     } else {
+                        // Pixel values are logical, convert them to framebuffer pixels
+                        let value = util::screen_pixel(value, Axis::Y, screen);
+
                         match side {
                             SideY::FromTop => {
                                 match from {
@@ -2406,10 +2430,10 @@ impl Constraints {
                                 match relative {
                                     ElementRef::This => {
                                         let constraints = MutRef::new(self as *mut Constraints);
-                                        util::centered(util::pixel_relative(value, axis, constraints), element_constraints.get_y(), element_constraints.get_height(), self.get_height())
+                                        util::centered(util::pixel_relative(value, axis, constraints, screen), element_constraints.get_y(), element_constraints.get_height(), self.get_height())
                                     }
                                     ElementRef::Parent => {
-                                        util::centered(util::pixel_relative(value, axis, element_constraints), element_constraints.get_y(), element_constraints.get_height(), self.get_height())
+                                        util::centered(util::pixel_relative(value, axis, element_constraints, screen), element_constraints.get_y(), element_constraints.get_height(), self.get_height())
                                     }
                                     ElementRef::Screen => {
                                         util::centered(util::screen_pixel_relative(value, axis, screen), element_constraints.get_y(), element_constraints.get_height(), self.get_height())
@@ -2417,7 +2441,7 @@ impl Constraints {
                                     ElementRef::Other(id) => {
                                         let mut element2 = system.get_entity_mut(id.clone());
                                         let element_constraints2 = element2.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", id.clone()).as_str());
-                                        util::centered(util::pixel_relative(value, axis, element_constraints2), element_constraints.get_y(), element_constraints.get_height(), self.get_height())
+                                        util::centered(util::pixel_relative(value, axis, element_constraints2, screen), element_constraints.get_y(), element_constraints.get_height(), self.get_height())
                                     }
                                 }
                             }
@@ -2425,12 +2449,12 @@ impl Constraints {
                                 match relative {
                                     ElementRef::This => {
                                         let constraints = MutRef::new(self as *mut Constraints);
-                                        util::centered(util::pixel_relative(value, axis, constraints), 0.0, screen.height as f32, self.get_height())
+                                        util::centered(util::pixel_relative(value, axis, constraints, screen), 0.0, screen.height as f32, self.get_height())
                                     }
                                     ElementRef::Parent => {
                                         let mut element = system.get_entity_mut(parent.clone().unwrap());
                                         let element_constraints = element.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", parent.clone().unwrap()).as_str());
-                                        util::centered(util::pixel_relative(value, axis, element_constraints), 0.0, screen.height as f32, self.get_height())
+                                        util::centered(util::pixel_relative(value, axis, element_constraints, screen), 0.0, screen.height as f32, self.get_height())
                                     }
                                     ElementRef::Screen => {
                                         util::centered(util::screen_pixel_relative(value, axis, screen), 0.0, screen.height as f32, self.get_height())
@@ -2438,7 +2462,7 @@ impl Constraints {
                                     ElementRef::Other(id) => {
                                         let mut element = system.get_entity_mut(id.clone());
                                         let element_constraints = element.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", id.clone()).as_str());
-                                        util::centered(util::pixel_relative(value, axis, element_constraints), 0.0, screen.height as f32, self.get_height())
+                                        util::centered(util::pixel_relative(value, axis, element_constraints, screen), 0.0, screen.height as f32, self.get_height())
                                     }
                                 }
                             }
@@ -2449,12 +2473,12 @@ impl Constraints {
                                 match relative {
                                     ElementRef::This => {
                                         let constraints = MutRef::new(self as *mut Constraints);
-                                        util::centered(util::pixel_relative(value, axis, constraints), element_constraints.get_y(), element_constraints.get_height(), self.get_height())
+                                        util::centered(util::pixel_relative(value, axis, constraints, screen), element_constraints.get_y(), element_constraints.get_height(), self.get_height())
                                     }
                                     ElementRef::Parent => {
                                         let mut element2 = system.get_entity_mut(parent.clone().unwrap());
                                         let element_constraints2 = element2.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", parent.clone().unwrap()).as_str());
-                                        util::centered(util::pixel_relative(value, axis, element_constraints2), element_constraints.get_y(), element_constraints.get_height(), self.get_height())
+                                        util::centered(util::pixel_relative(value, axis, element_constraints2, screen), element_constraints.get_y(), element_constraints.get_height(), self.get_height())
                                     }
                                     ElementRef::Screen => {
                                         util::centered(util::screen_pixel_relative(value, axis, screen), element_constraints.get_y(), element_constraints.get_height(), self.get_height())
@@ -2462,12 +2486,15 @@ impl Constraints {
                                     ElementRef::Other(id) => {
                                         let mut element2 = system.get_entity_mut(id.clone());
                                         let element_constraints2 = element2.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'", id.clone()).as_str());
-                                        util::centered(util::pixel_relative(value, axis, element_constraints2), element_constraints.get_y(), element_constraints.get_height(), self.get_height())
+                                        util::centered(util::pixel_relative(value, axis, element_constraints2, screen), element_constraints.get_y(), element_constraints.get_height(), self.get_height())
                                     }
                                 }
                             }
                         }
                     } else {
+                        // Pixel values are logical, convert them to framebuffer pixels
+                        let value = util::screen_pixel(value, Axis::Y, screen);
+
                         match from {
                             ElementRef::This => {
                                 warn!("Pepper/Constraints", "Recursive x constraint for '{}'", self.parent.clone().unwrap());
@@ -2889,45 +2916,28 @@ impl Constraints {
                         match element {
                             ElementRef::This => {
                                 if axis == Axis::Y {
-                                    value * self.get_scale_y()
+                                    util::pixel_relative(value, Axis::Y, MutRef::new(self as *mut Constraints), screen)
                                 } else {
                                     warn!("Pepper/Constraints", "Recursive width constraint for '{}'", self.parent.clone().unwrap());
                                     DEFAULT_SIZE
                                 }
                             }
                             ElementRef::Parent => {
-                                let mut parent_constraints = system.get_entity_mut(parent.clone().unwrap()).get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'!", parent.clone().unwrap()).as_str());
-                                match axis {
-                                    Axis::X => parent_constraints.get_scale_x() * value,
-                                    Axis::Y => parent_constraints.get_scale_y() * value,
-                                    Axis::Min => parent_constraints.get_scale_x().min(parent_constraints.get_scale_y()) * value,
-                                    Axis::Max => parent_constraints.get_scale_x().max(parent_constraints.get_scale_y()) * value,
-                                    Axis::Average => (parent_constraints.get_scale_x() + parent_constraints.get_scale_y()) / 2.0 * value,
-                                }
+                                let parent_constraints = system.get_entity_mut(parent.clone().unwrap()).get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'!", parent.clone().unwrap()).as_str());
+                                util::pixel_relative(value, axis, parent_constraints, screen)
                             }
                             ElementRef::Screen => {
-                                match axis {
-                                    Axis::X => screen.scale_x * value,
-                                    Axis::Y => screen.scale_y * value,
-                                    Axis::Min => screen.scale_x.min(screen.scale_y) * value,
-                                    Axis::Max => screen.scale_x.max(screen.scale_y) * value,
-                                    Axis::Average => (screen.scale_x + screen.scale_y) / 2.0 * value
-                                }
+                                util::screen_pixel_relative(value, axis, screen)
                             }
                             ElementRef::Other(id) => {
                                 let mut element = system.get_entity_mut(id.clone());
-                                let mut element_constraints = element.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraints Component not defined for '{}'!", id).as_str());
-                                match axis {
-                                    Axis::X => element_constraints.get_scale_x() * value,
-                                    Axis::Y => element_constraints.get_scale_y() * value,
-                                    Axis::Min => element_constraints.get_scale_x().min(element_constraints.get_scale_y()) * value,
-                                    Axis::Max => element_constraints.get_scale_x().max(element_constraints.get_scale_y()) * value,
-                                    Axis::Average => (element_constraints.get_scale_y() + element_constraints.get_scale_y()) / 2.0 * value,
-                                }
+                                let element_constraints = element.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraints Component not defined for '{}'!", id).as_str());
+                                util::pixel_relative(value, axis, element_constraints, screen)
                             }
                         }
                     } else {
-                        value
+                        // Pixel values are logical, convert them to framebuffer pixels
+                        util::screen_pixel(value, Axis::X, screen)
                     }
                 }
                 ConstraintWidth::Percent { value, relative } => {
@@ -3026,45 +3036,28 @@ impl Constraints {
                         match element {
                             ElementRef::This => {
                                 if axis == Axis::X {
-                                    value * self.get_scale_x()
+                                    util::pixel_relative(value, Axis::X, MutRef::new(self as *mut Constraints), screen)
                                 } else {
                                     warn!("Pepper/Constraints", "Recursive height constraint for '{}'", self.parent.clone().unwrap());
                                     DEFAULT_SIZE
                                 }
                             }
                             ElementRef::Parent => {
-                                let mut parent_constraints = system.get_entity_mut(parent.clone().unwrap()).get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'!", parent.clone().unwrap()).as_str());
-                                match axis {
-                                    Axis::X => parent_constraints.get_scale_x() * value,
-                                    Axis::Y => parent_constraints.get_scale_y() * value,
-                                    Axis::Min => parent_constraints.get_scale_x().min(parent_constraints.get_scale_y()) * value,
-                                    Axis::Max => parent_constraints.get_scale_x().max(parent_constraints.get_scale_y()) * value,
-                                    Axis::Average => (parent_constraints.get_scale_x() + parent_constraints.get_scale_y()) / 2.0 * value,
-                                }
+                                let parent_constraints = system.get_entity_mut(parent.clone().unwrap()).get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraint Component not defined for '{}'!", parent.clone().unwrap()).as_str());
+                                util::pixel_relative(value, axis, parent_constraints, screen)
                             }
                             ElementRef::Screen => {
-                                match axis {
-                                    Axis::X => screen.scale_x * value,
-                                    Axis::Y => screen.scale_y * value,
-                                    Axis::Min => screen.scale_x.min(screen.scale_y) * value,
-                                    Axis::Max => screen.scale_x.max(screen.scale_y) * value,
-                                    Axis::Average => (screen.scale_x + screen.scale_y) / 2.0 * value
-                                }
+                                util::screen_pixel_relative(value, axis, screen)
                             }
                             ElementRef::Other(id) => {
                                 let mut element = system.get_entity_mut(id.clone());
-                                let mut element_constraints = element.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraints Component not defined for '{}'!", id).as_str());
-                                match axis {
-                                    Axis::X => element_constraints.get_scale_x() * value,
-                                    Axis::Y => element_constraints.get_scale_y() * value,
-                                    Axis::Min => element_constraints.get_scale_x().min(element_constraints.get_scale_y()) * value,
-                                    Axis::Max => element_constraints.get_scale_x().max(element_constraints.get_scale_y()) * value,
-                                    Axis::Average => (element_constraints.get_scale_y() + element_constraints.get_scale_y()) / 2.0 * value,
-                                }
+                                let element_constraints = element.get_component_mut::<Constraints>().assume("Pepper/Constraints", format!("Constraints Component not defined for '{}'!", id).as_str());
+                                util::pixel_relative(value, axis, element_constraints, screen)
                             }
                         }
                     } else {
-                        value
+                        // Pixel values are logical, convert them to framebuffer pixels
+                        util::screen_pixel(value, Axis::Y, screen)
                     }
                 }
                 ConstraintHeight::Percent { value, relative } => {
@@ -3171,3 +3164,44 @@ impl Constraints {
     }
 
 }
+
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                              Tests                                             //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::IUIElement;
+
+    fn layout(content_scale: f32) -> (f32, f32) {
+        let mut system = get_system();
+        let mut screen = system.get_attribute_mut::<ScreenProperty>("screen").unwrap();
+        screen.content_scale_x = content_scale;
+        screen.content_scale_y = content_scale;
+
+        let mut anchor = system.get_entity_mut("Pepper/Anchor".to_string());
+        let mut anchor_constraints = anchor.get_constraints();
+        anchor_constraints.constraint_x = ConstraintX::pixel_in_left(10.0, ElementRef::Screen);
+        anchor_constraints.constraint_width = ConstraintWidth::pixel(100.0);
+        anchor_constraints.prepare();
+
+        let anchor_ref = ElementRef::Other("Pepper/Anchor".to_string());
+        let mut element = system.get_entity_mut("Pepper/Relative".to_string());
+        let mut constraints = element.get_constraints();
+        constraints.constraint_x = ConstraintX::pixel_relative_x_from_right(20.0, anchor_ref.clone(), anchor_ref.clone());
+        constraints.constraint_width = ConstraintWidth::pixel_relative_x(50.0, anchor_ref);
+        constraints.prepare();
+
+        return (constraints.get_x(), constraints.get_width());
+    }
+
+    #[test]
+    fn element_relative_pixels_follow_content_scale() {
+        assert_eq!(layout(1.0), (130.0, 50.0));
+        assert_eq!(layout(2.0), (260.0, 100.0));
+        layout(1.0);
+    }
+
+}
\ No newline at end of file
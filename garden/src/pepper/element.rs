@@ -1,6 +1,6 @@
 use std::any::TypeId;
 
-use crate::{ui::{UIComponent, ZIndexComponent, ParentComponent, SceneComponent, Constraints}, mem::MutRef, lettuce::Entity, potato::Assume};
+use crate::{ui::{UIComponent, ZIndexComponent, ParentComponent, SceneComponent, WindowComponent, Constraints}, mem::MutRef, lettuce::Entity, potato::Assume};
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 //                                           Element Ref                                          //
@@ -31,6 +31,7 @@ pub trait IUIElement {
     fn get_z_index(&mut self) -> MutRef<ZIndexComponent>;
     fn get_parent(&mut self) -> MutRef<ParentComponent>;
     fn get_scene(&mut self) -> MutRef<SceneComponent>;
+    fn get_window(&mut self) -> MutRef<WindowComponent>;
 
 }
 
@@ -77,4 +78,8 @@ impl IUIElement for UIElement {
         return self.get_component_mut::<SceneComponent>().assume("Pepper", format!("Scene Component not defined for '{}'!", self.id()).as_str());
     }
 
+    fn get_window(&mut self) -> MutRef<WindowComponent> {
+        return self.get_component_mut::<WindowComponent>().assume("Pepper", format!("Window Component not defined for '{}'!", self.id()).as_str());
+    }
+
 }
\ No newline at end of file
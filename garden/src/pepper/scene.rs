@@ -1,10 +1,13 @@
-use crate::{mem::MutRef, ui::{get_system, IUIElement, SceneComponent}, potato::Assume};
+use std::{collections::HashMap, ptr::{addr_of, addr_of_mut}};
+
+use crate::{mem::MutRef, ui::{get_system, IUIElement, SceneComponent, WindowComponent}, potato::Assume};
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 //                                              Scene                                             //
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 
 static mut SCENE: Option<String> = None;
+static mut WINDOW_SCENES: Option<HashMap<String, Option<String>>> = None;
 
 pub fn get_scene() -> Option<String> {
     unsafe {
@@ -18,6 +21,26 @@ pub fn set_scene(scene: Option<String>) {
     }
 }
 
+pub fn get_window_scene(window: Option<String>) -> Option<String> {
+    unsafe {
+        return if let Some(window) = window {
+            (*addr_of!(WINDOW_SCENES)).as_ref().and_then(|scenes| scenes.get(&window).cloned()).flatten()
+        } else {
+            get_scene()
+        }
+    }
+}
+
+pub fn set_window_scene(window: Option<String>, scene: Option<String>) {
+    unsafe {
+        if let Some(window) = window {
+            (*addr_of_mut!(WINDOW_SCENES)).get_or_insert_with(HashMap::new).insert(window, scene);
+        } else {
+            set_scene(scene);
+        }
+    }
+}
+
 pub fn prepare_elements() {
     for element in get_system().iter_entity_mut() {
         MutRef::from(element).prepare();
@@ -25,13 +48,22 @@ pub fn prepare_elements() {
 }
 
 pub fn update_scene(delta: f64) {
-    if get_scene().is_none() {
+    update_window_scene(None, delta);
+}
+
+pub fn update_window_scene(window: Option<String>, delta: f64) {
+    let scene = get_window_scene(window.clone());
+    if scene.is_none() {
         return;
     }
 
     for element in get_system().iter_entity_mut() {
-        if element.get_component::<SceneComponent>().assume("Pepper", format!("Scene Component not defined for '{}'!", element.id()).as_str()).0 == get_scene() {
+        if element.get_component::<WindowComponent>().assume("Pepper", format!("Window Component not defined for '{}'!", element.id()).as_str()).0 != window {
+            continue;
+        }
+
+        if element.get_component::<SceneComponent>().assume("Pepper", format!("Scene Component not defined for '{}'!", element.id()).as_str()).0 == scene {
             MutRef::from(element).update(delta);
         }
     }
-}
\ No newline at end of file
+}
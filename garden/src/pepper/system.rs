@@ -1,6 +1,6 @@
 use std::any::Any;
 
-use crate::{lettuce::{System, Entity}, ui::{Constraints, ZIndexComponent, ParentComponent, SceneComponent, UIComponent}, mem::MutRef};
+use crate::{lettuce::{System, Entity}, ui::{Constraints, ZIndexComponent, ParentComponent, SceneComponent, WindowComponent, UIComponent}, mem::MutRef};
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 //                                            UI System                                           //
@@ -25,6 +25,7 @@ pub fn get_system() -> MutRef<UISystem> {
                     entity.add_component(ZIndexComponent(0));
                     entity.add_component(ParentComponent(None));
                     entity.add_component(SceneComponent(None));
+                    entity.add_component(WindowComponent(None));
                 }, |entity: MutRef<Entity<String>>, component: &mut Box<dyn Any>| {
                     if component.is::<Box<dyn UIComponent>>() {
                         if let Some(comp) = component.downcast_mut::<Box<dyn UIComponent>>() {
@@ -36,7 +37,9 @@ pub fn get_system() -> MutRef<UISystem> {
                     width: 800,
                     height: 600,
                     scale_x: 1.0,
-                    scale_y: 1.0
+                    scale_y: 1.0,
+                    content_scale_x: 1.0,
+                    content_scale_y: 1.0,
                 });
                 system
             });
@@ -52,6 +55,22 @@ pub fn get_system() -> MutRef<UISystem> {
 pub struct ScreenProperty {
     pub width: u32,
     pub height: u32,
+    // Ratio of the current size to the initial size
     pub scale_x: f32,
     pub scale_y: f32,
+    // Monitor content scale
+    pub content_scale_x: f32,
+    pub content_scale_y: f32,
+}
+
+impl ScreenProperty {
+
+    pub fn get_logical_width(&self) -> f32 {
+        return self.width as f32 / self.content_scale_x;
+    }
+
+    pub fn get_logical_height(&self) -> f32 {
+        return self.height as f32 / self.content_scale_y;
+    }
+
 }
\ No newline at end of file
@@ -0,0 +1,439 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use rhai::{Dynamic, Engine, EvalAltResult, Module, INT};
+
+use crate::{
+    garlic::{Axis, Color, Effect, EffectMode, SideX, SideY},
+    lettuce::Entity,
+    mem::MutRef,
+    onion::App,
+    pepper::{
+        components::BackgroundComponent, get_scene, get_system, set_scene, ConstraintHeight, ConstraintWidth,
+        ConstraintX, ConstraintY, ElementRef, IUIElement,
+    },
+    tomato::input::{Key, MouseButton},
+    info, warn,
+};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                         Script Context                                         //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub(crate) enum Binding {
+    Key(Key, String),
+    Mouse(MouseButton, String),
+}
+
+// Shared between a script and the functions it calls, cleared when the script is reloaded
+#[derive(Default)]
+pub(crate) struct ScriptContext {
+    pub bindings: Vec<Binding>,
+    pub tweens: Vec<Option<Effect<f64>>>,
+}
+
+impl ScriptContext {
+
+    pub fn clear(&mut self) {
+        self.bindings.clear();
+        self.tweens.clear();
+    }
+
+    // Stopped tweens leave a free slot, their handle is given to the next tween
+    pub fn add_tween(&mut self, tween: Effect<f64>) -> usize {
+        if let Some(index) = self.tweens.iter().position(|tween| tween.is_none()) {
+            self.tweens[index] = Some(tween);
+            return index;
+        }
+        self.tweens.push(Some(tween));
+        return self.tweens.len() - 1;
+    }
+
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                             Element                                            //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+// Errors raised by script functions, reported with the script's position
+type ScriptResult<T> = Result<T, Box<EvalAltResult>>;
+
+// Pepper element seen from scripts, elements are created with create_element
+#[derive(Clone)]
+pub struct ScriptElement(String);
+
+impl ScriptElement {
+
+    // Looks the element up without creating it, a mistyped id is a script error
+    fn entity(&self) -> ScriptResult<MutRef<Entity<String>>> {
+        return get_system().query_entity_mut(|entity| entity.id() == &self.0)
+            .ok_or_else(|| format!("Unknown element '{}'", self.0).into());
+    }
+
+    fn get_x(&mut self) -> ScriptResult<f64> {
+        return Ok(self.entity()?.get_constraints().get_x() as f64);
+    }
+
+    fn get_y(&mut self) -> ScriptResult<f64> {
+        return Ok(self.entity()?.get_constraints().get_y() as f64);
+    }
+
+    fn get_width(&mut self) -> ScriptResult<f64> {
+        return Ok(self.entity()?.get_constraints().get_width() as f64);
+    }
+
+    fn get_height(&mut self) -> ScriptResult<f64> {
+        return Ok(self.entity()?.get_constraints().get_height() as f64);
+    }
+
+    fn set_x(&mut self, constraint: ConstraintX) -> ScriptResult<()> {
+        self.entity()?.get_constraints().constraint_x = constraint;
+        return Ok(());
+    }
+
+    fn set_y(&mut self, constraint: ConstraintY) -> ScriptResult<()> {
+        self.entity()?.get_constraints().constraint_y = constraint;
+        return Ok(());
+    }
+
+    fn set_width(&mut self, constraint: ConstraintWidth) -> ScriptResult<()> {
+        self.entity()?.get_constraints().constraint_width = constraint;
+        return Ok(());
+    }
+
+    fn set_height(&mut self, constraint: ConstraintHeight) -> ScriptResult<()> {
+        self.entity()?.get_constraints().constraint_height = constraint;
+        return Ok(());
+    }
+
+    fn get_z_index(&mut self) -> ScriptResult<INT> {
+        return Ok(self.entity()?.get_z_index().0 as INT);
+    }
+
+    fn set_z_index(&mut self, z_index: INT) -> ScriptResult<()> {
+        self.entity()?.get_z_index().0 = z_index as i32;
+        return Ok(());
+    }
+
+    fn get_scene(&mut self) -> ScriptResult<Dynamic> {
+        return Ok(self.entity()?.get_scene().0.clone().map_or(Dynamic::UNIT, Dynamic::from));
+    }
+
+    fn set_scene(&mut self, scene: Dynamic) -> ScriptResult<()> {
+        self.entity()?.get_scene().0 = scene.into_string().ok();
+        return Ok(());
+    }
+
+    fn set_background(&mut self, color: Color) -> ScriptResult<()> {
+        let mut element = self.entity()?;
+        if let Some(mut background) = element.get_ui_component_mut::<BackgroundComponent>() {
+            background.color.set_fixed(color);
+        } else {
+            element.add_ui_component(BackgroundComponent::new(color));
+        }
+        return Ok(());
+    }
+
+    fn animate_background(&mut self, start: Color, target: Color, duration: f64, mode: &str, repeat: bool) -> ScriptResult<()> {
+        let mut element = self.entity()?;
+        if element.get_ui_component_mut::<BackgroundComponent>().is_none() {
+            element.add_ui_component(BackgroundComponent::new(start.clone()));
+        }
+        element.get_ui_component_mut::<BackgroundComponent>().unwrap().color.set_animation(start, target, duration, parse_mode(mode), repeat).start();
+        return Ok(());
+    }
+
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                             Parsing                                            //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+// "screen", "parent", "this" or the id of another element
+fn parse_ref(name: &str) -> ElementRef {
+    return match name {
+        "screen" => ElementRef::Screen,
+        "parent" => ElementRef::Parent,
+        "this" => ElementRef::This,
+        other => ElementRef::Other(other.to_string()),
+    };
+}
+
+fn parse_axis(name: &str) -> Axis {
+    return match name {
+        "x" => Axis::X,
+        "y" => Axis::Y,
+        "min" => Axis::Min,
+        "max" => Axis::Max,
+        "avg" | "average" => Axis::Average,
+        _ => {
+            warn!("Radish", "Unknown axis '{}', using 'x'!", name);
+            Axis::X
+        }
+    };
+}
+
+fn parse_mode(name: &str) -> EffectMode {
+    return match name {
+        "linear" => EffectMode::Linear,
+        "ease_in" => EffectMode::EaseIn,
+        "ease_out" => EffectMode::EaseOut,
+        "ease_in_out" => EffectMode::EaseInOut,
+        _ => {
+            warn!("Radish", "Unknown effect mode '{}', using 'linear'!", name);
+            EffectMode::Linear
+        }
+    };
+}
+
+// Names follow the Key variants, letters & digits can be written alone
+pub(crate) fn parse_key(name: &str) -> Option<Key> {
+    let key = match name.to_lowercase().as_str() {
+        "a" => Key::A, "b" => Key::B, "c" => Key::C, "d" => Key::D, "e" => Key::E, "f" => Key::F,
+        "g" => Key::G, "h" => Key::H, "i" => Key::I, "j" => Key::J, "k" => Key::K, "l" => Key::L,
+        "m" => Key::M, "n" => Key::N, "o" => Key::O, "p" => Key::P, "q" => Key::Q, "r" => Key::R,
+        "s" => Key::S, "t" => Key::T, "u" => Key::U, "v" => Key::V, "w" => Key::W, "x" => Key::X,
+        "y" => Key::Y, "z" => Key::Z,
+        "0" | "num0" => Key::Num0, "1" | "num1" => Key::Num1, "2" | "num2" => Key::Num2,
+        "3" | "num3" => Key::Num3, "4" | "num4" => Key::Num4, "5" | "num5" => Key::Num5,
+        "6" | "num6" => Key::Num6, "7" | "num7" => Key::Num7, "8" | "num8" => Key::Num8,
+        "9" | "num9" => Key::Num9,
+        "f1" => Key::F1, "f2" => Key::F2, "f3" => Key::F3, "f4" => Key::F4, "f5" => Key::F5,
+        "f6" => Key::F6, "f7" => Key::F7, "f8" => Key::F8, "f9" => Key::F9, "f10" => Key::F10,
+        "f11" => Key::F11, "f12" => Key::F12,
+        "space" => Key::Space,
+        "enter" => Key::Enter,
+        "escape" => Key::Escape,
+        "tab" => Key::Tab,
+        "backspace" => Key::Backspace,
+        "delete" => Key::Delete,
+        "insert" => Key::Insert,
+        "home" => Key::Home,
+        "end" => Key::End,
+        "pageup" => Key::PageUp,
+        "pagedown" => Key::PageDown,
+        "left" => Key::Left,
+        "right" => Key::Right,
+        "up" => Key::Up,
+        "down" => Key::Down,
+        "leftshift" => Key::LeftShift,
+        "rightshift" => Key::RightShift,
+        "leftcontrol" => Key::LeftControl,
+        "rightcontrol" => Key::RightControl,
+        "leftalt" => Key::LeftAlt,
+        "rightalt" => Key::RightAlt,
+        _ => return None,
+    };
+    return Some(key);
+}
+
+pub(crate) fn parse_button(name: &str) -> Option<MouseButton> {
+    return match name.to_lowercase().as_str() {
+        "left" => Some(MouseButton::Button1),
+        "right" => Some(MouseButton::Button2),
+        "middle" => Some(MouseButton::Button3),
+        _ => None,
+    };
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                              API                                               //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub(crate) fn register_api(engine: &mut Engine, context: Rc<RefCell<ScriptContext>>) {
+    register_logging(engine);
+    register_color(engine);
+    register_constraints(engine);
+    register_elements(engine);
+    register_input(engine, context.clone());
+    register_tweens(engine, context);
+}
+
+fn register_logging(engine: &mut Engine) {
+    engine.on_print(|message| info!("Script", "{}", message));
+    engine.on_debug(|message, source, position| info!("Script", "{} {:?} {}", source.unwrap_or(""), position, message));
+    engine.register_fn("warn", |message: &str| warn!("Script", "{}", message));
+    engine.register_fn("time", || App::get().time().get_time());
+}
+
+fn register_color(engine: &mut Engine) {
+    engine.register_type_with_name::<Color>("Color")
+        .register_fn("rgb", |red: f64, green: f64, blue: f64| Color::from_rgb(red as f32, green as f32, blue as f32))
+        .register_fn("rgba", |red: f64, green: f64, blue: f64, alpha: f64| Color::from_rgba(red as f32, green as f32, blue as f32, alpha as f32))
+        .register_fn("rgb_hex", |rgb: INT| Color::from_rgb_hex(rgb))
+        .register_fn("rgba_hex", |rgba: INT| Color::from_rgba_hex(rgba))
+        .register_get("red", |color: &mut Color| color.red as f64)
+        .register_get("green", |color: &mut Color| color.green as f64)
+        .register_get("blue", |color: &mut Color| color.blue as f64)
+        .register_get("alpha", |color: &mut Color| color.alpha as f64);
+}
+
+// ConstraintX::pixel_in_left(20.0, "screen") mirrors the Rust constructors
+fn register_constraints(engine: &mut Engine) {
+    engine.register_type_with_name::<ConstraintX>("ConstraintX");
+    engine.register_type_with_name::<ConstraintY>("ConstraintY");
+    engine.register_type_with_name::<ConstraintWidth>("ConstraintWidth");
+    engine.register_type_with_name::<ConstraintHeight>("ConstraintHeight");
+
+    let mut x = Module::new();
+    for (name, side) in [("in_left", SideX::InLeft), ("in_right", SideX::InRight), ("from_left", SideX::FromLeft), ("from_right", SideX::FromRight)] {
+        x.set_native_fn(format!("pixel_{}", name), move |value: f64, from: &str| Ok(ConstraintX::pixel(value as f32, (side, parse_ref(from)))));
+        x.set_native_fn(format!("percent_{}", name), move |value: f64, axis: &str, relative: &str, from: &str| {
+            Ok(ConstraintX::percent(value as f32, (parse_axis(axis), parse_ref(relative)), (side, parse_ref(from))))
+        });
+    }
+    x.set_native_fn("pixel_centered", |value: f64, from: &str| Ok(ConstraintX::pixel_centered(value as f32, parse_ref(from))));
+    x.set_native_fn("percent_centered", |value: f64, axis: &str, relative: &str, from: &str| {
+        Ok(ConstraintX::percent_centered(value as f32, parse_ref(from), (parse_axis(axis), parse_ref(relative))))
+    });
+    engine.register_static_module("ConstraintX", x.into());
+
+    let mut y = Module::new();
+    for (name, side) in [("in_top", SideY::InTop), ("in_bottom", SideY::InBottom), ("from_top", SideY::FromTop), ("from_bottom", SideY::FromBottom)] {
+        y.set_native_fn(format!("pixel_{}", name), move |value: f64, from: &str| Ok(ConstraintY::pixel(value as f32, (side, parse_ref(from)))));
+        y.set_native_fn(format!("percent_{}", name), move |value: f64, axis: &str, relative: &str, from: &str| {
+            Ok(ConstraintY::percent(value as f32, (parse_axis(axis), parse_ref(relative)), (side, parse_ref(from))))
+        });
+    }
+    y.set_native_fn("pixel_centered", |value: f64, from: &str| Ok(ConstraintY::pixel_centered(value as f32, parse_ref(from))));
+    y.set_native_fn("percent_centered", |value: f64, axis: &str, relative: &str, from: &str| {
+        Ok(ConstraintY::percent_centered(value as f32, parse_ref(from), (parse_axis(axis), parse_ref(relative))))
+    });
+    engine.register_static_module("ConstraintY", y.into());
+
+    let mut width = Module::new();
+    width.set_native_fn("pixel", |value: f64| Ok(ConstraintWidth::pixel(value as f32)));
+    width.set_native_fn("pixel_relative", |value: f64, axis: &str, relative: &str| Ok(ConstraintWidth::pixel_relative(value as f32, (parse_axis(axis), parse_ref(relative)))));
+    width.set_native_fn("percent", |value: f64, axis: &str, relative: &str| Ok(ConstraintWidth::percent(value as f32, (parse_axis(axis), parse_ref(relative)))));
+    engine.register_static_module("ConstraintWidth", width.into());
+
+    let mut height = Module::new();
+    height.set_native_fn("pixel", |value: f64| Ok(ConstraintHeight::pixel(value as f32)));
+    height.set_native_fn("pixel_relative", |value: f64, axis: &str, relative: &str| Ok(ConstraintHeight::pixel_relative(value as f32, (parse_axis(axis), parse_ref(relative)))));
+    height.set_native_fn("percent", |value: f64, axis: &str, relative: &str| Ok(ConstraintHeight::percent(value as f32, (parse_axis(axis), parse_ref(relative)))));
+    engine.register_static_module("ConstraintHeight", height.into());
+}
+
+fn register_elements(engine: &mut Engine) {
+    engine.register_type_with_name::<ScriptElement>("Element")
+        .register_fn("element", |id: &str| -> ScriptResult<ScriptElement> {
+            let element = ScriptElement(id.to_string());
+            element.entity()?;
+            return Ok(element);
+        })
+        .register_fn("create_element", |id: &str| {
+            get_system().get_entity_mut(id.to_string());
+            return ScriptElement(id.to_string());
+        })
+        .register_get("id", |element: &mut ScriptElement| element.0.clone())
+        .register_get("x", ScriptElement::get_x)
+        .register_get("y", ScriptElement::get_y)
+        .register_get("width", ScriptElement::get_width)
+        .register_get("height", ScriptElement::get_height)
+        .register_set("constraint_x", ScriptElement::set_x)
+        .register_set("constraint_y", ScriptElement::set_y)
+        .register_set("constraint_width", ScriptElement::set_width)
+        .register_set("constraint_height", ScriptElement::set_height)
+        .register_get_set("z_index", ScriptElement::get_z_index, ScriptElement::set_z_index)
+        .register_get_set("scene", ScriptElement::get_scene, ScriptElement::set_scene)
+        .register_fn("set_background", ScriptElement::set_background)
+        .register_fn("animate_background", ScriptElement::animate_background);
+
+    engine.register_fn("get_scene", || get_scene().map_or(Dynamic::UNIT, Dynamic::from));
+    engine.register_fn("set_scene", |scene: Dynamic| set_scene(scene.into_string().ok()));
+}
+
+// Bound functions are called with no arguments when the key or button is pressed
+fn register_input(engine: &mut Engine, context: Rc<RefCell<ScriptContext>>) {
+    let keys = context.clone();
+    engine.register_fn("bind_key", move |name: &str, callback: &str| {
+        if let Some(key) = parse_key(name) {
+            keys.borrow_mut().bindings.push(Binding::Key(key, callback.to_string()));
+        } else {
+            warn!("Radish", "Unknown key '{}'!", name);
+        }
+    });
+    let buttons = context.clone();
+    engine.register_fn("bind_mouse", move |name: &str, callback: &str| {
+        if let Some(button) = parse_button(name) {
+            buttons.borrow_mut().bindings.push(Binding::Mouse(button, callback.to_string()));
+        } else {
+            warn!("Radish", "Unknown mouse button '{}'!", name);
+        }
+    });
+    engine.register_fn("unbind", move |callback: &str| {
+        context.borrow_mut().bindings.retain(|binding| match binding {
+            Binding::Key(_, name) | Binding::Mouse(_, name) => name != callback,
+        });
+    });
+
+    engine.register_fn("is_key_down", |name: &str| {
+        return parse_key(name).is_some_and(|key| App::get().window().input().keyboard().get_key(key).is_pressed_or_down());
+    });
+    engine.register_fn("is_key_pressed", |name: &str| {
+        return parse_key(name).is_some_and(|key| App::get().window().input().keyboard().get_key(key).is_pressed());
+    });
+    engine.register_fn("mouse_x", || App::get().window().input().mouse().get_x());
+    engine.register_fn("mouse_y", || App::get().window().input().mouse().get_y());
+}
+
+// Tweens are Effect animations owned by the script, referred to by handle
+fn register_tweens(engine: &mut Engine, context: Rc<RefCell<ScriptContext>>) {
+    let spawn = context.clone();
+    engine.register_fn("tween", move |start: f64, target: f64, duration: f64, mode: &str, repeat: bool| {
+        let mut effect = Effect::new(start);
+        effect.set_animation(start, target, duration, parse_mode(mode), repeat).start();
+        return spawn.borrow_mut().add_tween(effect) as INT;
+    });
+    let value = context.clone();
+    engine.register_fn("tween_value", move |handle: INT| {
+        return value.borrow().tweens.get(handle as usize).and_then(|tween| tween.as_ref()).map_or(0.0, |tween| tween.current());
+    });
+    let running = context.clone();
+    engine.register_fn("tween_running", move |handle: INT| {
+        return running.borrow().tweens.get(handle as usize).and_then(|tween| tween.as_ref()).is_some_and(|tween| tween.is_running());
+    });
+    engine.register_fn("stop_tween", move |handle: INT| {
+        if let Some(tween) = context.borrow_mut().tweens.get_mut(handle as usize) {
+            *tween = None;
+        }
+    });
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                              Tests                                             //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reuses_stopped_tween_slots() {
+        let mut context = ScriptContext::default();
+        assert_eq!(context.add_tween(Effect::new(0.0)), 0);
+        assert_eq!(context.add_tween(Effect::new(1.0)), 1);
+
+        context.tweens[0] = None;
+        assert_eq!(context.add_tween(Effect::new(2.0)), 0);
+        assert_eq!(context.add_tween(Effect::new(3.0)), 2);
+        assert_eq!(context.tweens.len(), 3);
+    }
+
+    #[test]
+    fn elements_are_not_created_by_lookup() {
+        let mut engine = Engine::new();
+        register_api(&mut engine, Rc::new(RefCell::new(ScriptContext::default())));
+
+        assert!(engine.eval::<ScriptElement>(r#"element("Radish/Missing")"#).is_err());
+        assert!(get_system().query_entity(|entity| entity.id() == "Radish/Missing").is_none());
+
+        let z_index = engine.eval::<INT>(r#"
+            let created = create_element("Radish/Created");
+            created.z_index = 3;
+            element("Radish/Created").z_index
+        "#).unwrap();
+        assert_eq!(z_index, 3);
+    }
+
+}
\ No newline at end of file
@@ -0,0 +1,5 @@
+mod api;
+mod script;
+pub use script::*;
+mod state;
+pub use state::*;
\ No newline at end of file
@@ -0,0 +1,170 @@
+use std::cell::RefCell;
+use std::fs;
+use std::rc::Rc;
+use std::time::SystemTime;
+
+use rhai::{CallFnOptions, Dynamic, Engine, FuncArgs, Map, Scope, AST};
+
+use crate::{error, info, onion::App};
+
+use super::api::{register_api, Binding, ScriptContext};
+
+// Seconds between two checks of the script's modification time
+const RELOAD_INTERVAL: f64 = 0.25;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                             Script                                             //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+// Rhai script with IState-like callbacks, each one receives the script's state as `this`
+pub struct Script {
+    path: String,
+    engine: Engine,
+    ast: Option<AST>,
+    scope: Scope<'static>,
+    // Object map kept across reloads, scripts store their state in it
+    this: Dynamic,
+    context: Rc<RefCell<ScriptContext>>,
+    modified: Option<SystemTime>,
+    reload_timer: f64,
+    hot_reload: bool,
+}
+
+impl Script {
+
+    pub fn load(path: &str) -> Script {
+        let context = Rc::new(RefCell::new(ScriptContext::default()));
+        let mut engine = Engine::new();
+        register_api(&mut engine, context.clone());
+
+        let mut script = Script {
+            path: path.to_string(),
+            engine,
+            ast: None,
+            scope: Scope::new(),
+            this: Dynamic::from_map(Map::new()),
+            context,
+            modified: None,
+            reload_timer: 0.0,
+            hot_reload: true,
+        };
+        script.compile();
+        return script;
+    }
+
+    // Keeps the previous version running when the new one fails to compile
+    fn compile(&mut self) -> bool {
+        self.modified = fs::metadata(&self.path).and_then(|metadata| metadata.modified()).ok();
+
+        let source = match fs::read_to_string(&self.path) {
+            Ok(source) => source,
+            Err(err) => {
+                error!("Radish", "Failed to read script '{}', {}", self.path, err);
+                return false;
+            }
+        };
+        let ast = match self.engine.compile(source) {
+            Ok(ast) => ast,
+            Err(err) => {
+                error!("Radish", "Failed to compile script '{}', {}", self.path, err);
+                return false;
+            }
+        };
+
+        // Bindings & tweens are recreated by the new version
+        self.context.borrow_mut().clear();
+        self.scope = Scope::new();
+        if let Err(err) = self.engine.run_ast_with_scope(&mut self.scope, &ast) {
+            error!("Radish", "Failed to run script '{}', {}", self.path, err);
+        }
+        self.ast = Some(ast);
+        info!("Radish", "Script '{}' loaded!", self.path);
+        return true;
+    }
+
+    // Calls `reload()` if defined, the state in `this` is kept
+    pub fn reload(&mut self) {
+        if self.compile() {
+            self.call("reload", ());
+        }
+    }
+
+    fn check_reload(&mut self, delta: f64) {
+        if !self.hot_reload {
+            return;
+        }
+
+        self.reload_timer += delta;
+        if self.reload_timer < RELOAD_INTERVAL {
+            return;
+        }
+        self.reload_timer = 0.0;
+
+        let modified = fs::metadata(&self.path).and_then(|metadata| metadata.modified()).ok();
+        if modified.is_some() && modified != self.modified {
+            self.reload();
+        }
+    }
+
+    pub fn has_fn(&self, name: &str) -> bool {
+        return self.ast.as_ref().is_some_and(|ast| ast.iter_functions().any(|function| function.name == name));
+    }
+
+    // Missing functions are skipped, runtime errors are logged
+    pub fn call(&mut self, name: &str, args: impl FuncArgs) -> Option<Dynamic> {
+        if !self.has_fn(name) {
+            return None;
+        }
+
+        let options = CallFnOptions::new().eval_ast(false).bind_this_ptr(&mut self.this);
+        return match self.engine.call_fn_with_options(options, &mut self.scope, self.ast.as_ref().unwrap(), name, args) {
+            Ok(value) => Some(value),
+            Err(err) => {
+                error!("Radish", "'{}' failed in script '{}', {}", name, self.path, err);
+                None
+            }
+        };
+    }
+
+    // Reloads if needed, runs input bindings & tweens, then calls `update(delta)`
+    pub fn update(&mut self, delta: f64) {
+        self.check_reload(delta);
+
+        let callbacks: Vec<String> = {
+            let mut context = self.context.borrow_mut();
+            for tween in context.tweens.iter_mut().flatten() {
+                tween.update();
+            }
+
+            let window = App::get().window();
+            let input = window.input();
+            context.bindings.iter().filter_map(|binding| match binding {
+                Binding::Key(key, callback) => input.keyboard().get_key(*key).is_pressed().then(|| callback.clone()),
+                Binding::Mouse(button, callback) => input.mouse().get_button(*button).is_pressed().then(|| callback.clone()),
+            }).collect()
+        };
+        for callback in callbacks {
+            self.call(&callback, ());
+        }
+
+        self.call("update", (delta,));
+    }
+
+    // Getters & Setters
+    pub fn get_path(&self) -> &str {
+        return &self.path;
+    }
+
+    pub fn get_state(&self) -> &Dynamic {
+        return &self.this;
+    }
+
+    pub fn is_loaded(&self) -> bool {
+        return self.ast.is_some();
+    }
+
+    pub fn set_hot_reload(&mut self, hot_reload: bool) {
+        self.hot_reload = hot_reload;
+    }
+
+}
\ No newline at end of file
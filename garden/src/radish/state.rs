@@ -0,0 +1,92 @@
+use std::path::PathBuf;
+
+use rhai::Array;
+
+use crate::onion::IState;
+
+use super::Script;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                          Script State                                          //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+// Forwards every IState callback to the script, wrap a Script in your own state to register several
+pub struct ScriptState {
+    script: Script,
+}
+
+impl ScriptState {
+
+    pub fn new(path: &str) -> ScriptState {
+        return ScriptState {
+            script: Script::load(path),
+        };
+    }
+
+    pub fn script(&mut self) -> &mut Script {
+        return &mut self.script;
+    }
+
+}
+
+impl IState for ScriptState {
+
+    fn init(&mut self) {
+        self.script.call("init", ());
+    }
+
+    fn open(&mut self) {
+        self.script.call("open", ());
+    }
+
+    fn update(&mut self, delta: f64) {
+        self.script.update(delta);
+    }
+
+    fn close(&mut self) {
+        self.script.call("close", ());
+    }
+
+    fn dispose(&mut self) {
+        self.script.call("dispose", ());
+    }
+
+    fn pause(&mut self) {
+        self.script.call("pause", ());
+    }
+
+    fn resume(&mut self) {
+        self.script.call("resume", ());
+    }
+
+    fn fixed_update(&mut self, delta: f64) {
+        self.script.call("fixed_update", (delta,));
+    }
+
+    fn render(&mut self, alpha: f64) {
+        self.script.call("render", (alpha,));
+    }
+
+    fn resize(&mut self, width: u32, height: u32) {
+        self.script.call("resize", (width as rhai::INT, height as rhai::INT));
+    }
+
+    fn focus(&mut self, focused: bool) {
+        self.script.call("focus", (focused,));
+    }
+
+    fn minimize(&mut self, minimized: bool) {
+        self.script.call("minimize", (minimized,));
+    }
+
+    fn file_drop(&mut self, paths: &[PathBuf]) {
+        let paths: Array = paths.iter().map(|path| path.to_string_lossy().to_string().into()).collect();
+        self.script.call("file_drop", (paths,));
+    }
+
+    // Vetoed when the script returns false
+    fn close_requested(&mut self) -> bool {
+        return self.script.call("close_requested", ()).and_then(|value| value.as_bool().ok()).unwrap_or(true);
+    }
+
+}
\ No newline at end of file
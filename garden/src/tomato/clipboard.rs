@@ -0,0 +1,26 @@
+use std::ptr::addr_of;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                            Clipboard                                           //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+// In-process clipboard, used by headless windows which have no system clipboard
+static mut CLIPBOARD: Option<String> = None;
+
+pub fn get_local_clipboard() -> Option<String> {
+    unsafe {
+        return (*addr_of!(CLIPBOARD)).clone();
+    }
+}
+
+pub fn set_local_clipboard(text: &str) {
+    unsafe {
+        CLIPBOARD = Some(text.to_string());
+    }
+}
+
+pub fn clear_local_clipboard() {
+    unsafe {
+        CLIPBOARD = None;
+    }
+}
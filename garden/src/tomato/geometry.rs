@@ -0,0 +1,201 @@
+use std::fs;
+
+use crate::{error, warn};
+
+use super::Monitor;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                         Window Geometry                                        //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Clone, PartialEq)]
+pub struct WindowGeometry {
+    // Position & size in screen coordinates, when not maximized
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub maximized: bool,
+    pub monitor: Option<String>,
+}
+
+impl WindowGeometry {
+
+    pub fn load(path: &str) -> Option<WindowGeometry> {
+        // No file yet, nothing to restore
+        let src = fs::read_to_string(path).ok()?;
+
+        let mut geometry = WindowGeometry {
+            x: 0,
+            y: 0,
+            width: 0,
+            height: 0,
+            maximized: false,
+            monitor: None,
+        };
+        for line in src.lines() {
+            let (key, value) = match line.split_once('=') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None => continue,
+            };
+
+            let parsed = match key {
+                "x" => value.parse().map(|x| geometry.x = x).is_ok(),
+                "y" => value.parse().map(|y| geometry.y = y).is_ok(),
+                "width" => value.parse().map(|width| geometry.width = width).is_ok(),
+                "height" => value.parse().map(|height| geometry.height = height).is_ok(),
+                "maximized" => value.parse().map(|maximized| geometry.maximized = maximized).is_ok(),
+                "monitor" => {
+                    geometry.monitor = Some(value.to_string());
+                    true
+                }
+                _ => true,
+            };
+            if !parsed {
+                warn!("Tomato", "Invalid value '{}' for '{}' in '{}'!", value, key, path);
+                return None;
+            }
+        }
+
+        if geometry.width == 0 || geometry.height == 0 {
+            warn!("Tomato", "Invalid window size in '{}'!", path);
+            return None;
+        }
+
+        return Some(geometry);
+    }
+
+    pub fn save(&self, path: &str) {
+        let mut src = format!(
+            "x={}\ny={}\nwidth={}\nheight={}\nmaximized={}\n",
+            self.x, self.y, self.width, self.height, self.maximized
+        );
+        if let Some(monitor) = &self.monitor {
+            src.push_str(format!("monitor={}\n", monitor).as_str());
+        }
+
+        if let Err(err) = fs::write(path, src) {
+            error!("Tomato", "Failed to save window geometry to '{}', {}", path, err);
+        }
+    }
+
+    // Moves the window back onto a monitor if its title bar isn't visible anymore
+    pub fn clamp(&mut self, monitors: &[Monitor]) {
+        if monitors.iter().any(|monitor| monitor.contains(self.x + self.width as i32 / 2, self.y)) {
+            return;
+        }
+
+        let monitor = monitors.iter()
+            .find(|monitor| Some(&monitor.name) == self.monitor.as_ref())
+            .or_else(|| monitors.iter().find(|monitor| monitor.primary))
+            .or_else(|| monitors.first());
+        if let Some(monitor) = monitor {
+            self.width = self.width.min(monitor.work_width.max(1) as u32);
+            self.height = self.height.min(monitor.work_height.max(1) as u32);
+            self.x = self.x.clamp(monitor.work_x, monitor.work_x + monitor.work_width - self.width as i32);
+            self.y = self.y.clamp(monitor.work_y, monitor.work_y + monitor.work_height - self.height as i32);
+            self.monitor = Some(monitor.name.clone());
+        }
+    }
+
+}
+
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                              Tests                                             //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    fn monitor(name: &str, primary: bool, x: i32, y: i32, width: i32, height: i32) -> Monitor {
+        return Monitor {
+            index: 0,
+            name: name.to_string(),
+            primary,
+            x,
+            y,
+            physical_width: 0,
+            physical_height: 0,
+            scale_x: 1.0,
+            scale_y: 1.0,
+            work_x: x,
+            work_y: y,
+            work_width: width,
+            work_height: height,
+            video_mode: None,
+            video_modes: Vec::new(),
+        };
+    }
+
+    fn load_src(name: &str, src: &str) -> Option<WindowGeometry> {
+        let path = env::temp_dir().join(format!("garden_geometry_{}.txt", name));
+        let path = path.to_str().unwrap();
+        fs::write(path, src).unwrap();
+        let geometry = WindowGeometry::load(path);
+        fs::remove_file(path).unwrap();
+        return geometry;
+    }
+
+    #[test]
+    fn save_and_load() {
+        let geometry = WindowGeometry {
+            x: -20,
+            y: 40,
+            width: 1280,
+            height: 720,
+            maximized: true,
+            monitor: Some("DP-1".to_string()),
+        };
+        let path = env::temp_dir().join("garden_geometry_save.txt");
+        let path = path.to_str().unwrap();
+        geometry.save(path);
+        let loaded = WindowGeometry::load(path);
+        fs::remove_file(path).unwrap();
+        assert!(loaded == Some(geometry));
+    }
+
+    #[test]
+    fn load_rejects_invalid_files() {
+        assert!(WindowGeometry::load("garden_geometry_missing.txt").is_none());
+        assert!(load_src("bad_value", "x=left\nwidth=800\nheight=600\n").is_none());
+        assert!(load_src("no_size", "x=10\ny=10\n").is_none());
+
+        // Unknown keys and lines without a value are ignored
+        let geometry = load_src("unknown", "# comment\nwidth=800\nheight=600\ntheme=dark\n").unwrap();
+        assert_eq!((geometry.width, geometry.height, geometry.maximized), (800, 600, false));
+    }
+
+    #[test]
+    fn clamp_keeps_visible_window() {
+        let monitors = [monitor("A", true, 0, 0, 1920, 1080)];
+        let mut geometry = WindowGeometry { x: 100, y: 100, width: 800, height: 600, maximized: false, monitor: None };
+        geometry.clamp(&monitors);
+        assert_eq!((geometry.x, geometry.y), (100, 100));
+        assert!(geometry.monitor.is_none());
+    }
+
+    #[test]
+    fn clamp_moves_window_back_on_screen() {
+        let monitors = [
+            monitor("A", true, 0, 0, 1920, 1080),
+            monitor("B", false, 1920, 0, 1280, 1024),
+        ];
+
+        // The saved monitor is gone, fall back to the primary one
+        let mut geometry = WindowGeometry { x: 5000, y: 200, width: 800, height: 600, maximized: false, monitor: Some("C".to_string()) };
+        geometry.clamp(&monitors);
+        assert_eq!((geometry.x, geometry.y), (1120, 200));
+        assert_eq!(geometry.monitor.as_deref(), Some("A"));
+
+        // The saved monitor is preferred, the window is shrunk to its work area
+        let mut geometry = WindowGeometry { x: -3000, y: -500, width: 2000, height: 1200, maximized: false, monitor: Some("B".to_string()) };
+        geometry.clamp(&monitors);
+        assert_eq!((geometry.x, geometry.y, geometry.width, geometry.height), (1920, 0, 1280, 1024));
+        assert_eq!(geometry.monitor.as_deref(), Some("B"));
+    }
+
+}
\ No newline at end of file
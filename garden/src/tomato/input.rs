@@ -68,31 +68,127 @@ impl Action {
 
 }
 
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                              Drag                                              //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum DragState {
+    Started,
+    Dragging,
+    Ended,
+    None
+}
+
+impl DragState {
+
+    fn update(&mut self) {
+        match self {
+            DragState::Started => *self = DragState::Dragging,
+            DragState::Ended => *self = DragState::None,
+            _ => {}
+        }
+    }
+
+    pub fn is_started(&self) -> bool {
+        return *self == DragState::Started;
+    }
+
+    pub fn is_dragging(&self) -> bool {
+        return *self == DragState::Started || *self == DragState::Dragging;
+    }
+
+    pub fn is_ended(&self) -> bool {
+        return *self == DragState::Ended;
+    }
+
+}
+
+#[derive(Copy, Clone)]
+pub struct Drag {
+    pub state: DragState,
+    pub start_x: f64,
+    pub start_y: f64,
+    pub end_x: f64,
+    pub end_y: f64,
+}
+
+impl Drag {
+
+    fn new() -> Drag {
+        return Drag {
+            state: DragState::None,
+            start_x: 0.0,
+            start_y: 0.0,
+            end_x: 0.0,
+            end_y: 0.0,
+        }
+    }
+
+    pub fn get_dx(&self) -> f64 {
+        return self.end_x - self.start_x;
+    }
+
+    pub fn get_dy(&self) -> f64 {
+        return self.end_y - self.start_y;
+    }
+
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 //                                              Mouse                                             //
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 
+const MOUSE_BUTTON_COUNT: usize = (glfw::ffi::MOUSE_BUTTON_LAST + 1) as usize;
+
 pub struct Mouse {
-    pub(crate) x: i32,
-    pub(crate) y: i32,
-    pub(crate) last_x: i32,
-    pub(crate) last_y: i32,
+    // Position in framebuffer space
+    pub(crate) x: f64,
+    pub(crate) y: f64,
+    pub(crate) last_x: f64,
+    pub(crate) last_y: f64,
+    // Content scale, maps framebuffer space into logical pixels
+    pub(crate) scale_x: f64,
+    pub(crate) scale_y: f64,
     pub(crate) scroll_x: f64,
     pub(crate) scroll_y: f64,
-    pub(crate) buttons: [Action; (glfw::ffi::MOUSE_BUTTON_LAST + 1) as usize],
+    pub(crate) buttons: [Action; MOUSE_BUTTON_COUNT],
+    // Clicks
+    pub click_interval: f64,
+    pub click_slop: f64,
+    clicks: [u32; MOUSE_BUTTON_COUNT],
+    last_click_time: [f64; MOUSE_BUTTON_COUNT],
+    last_click_pos: [(f64, f64); MOUSE_BUTTON_COUNT],
+    // Drags
+    pressed: [bool; MOUSE_BUTTON_COUNT],
+    drags: [Drag; MOUSE_BUTTON_COUNT],
+    // Hover
+    in_window: bool,
+    hover_changed: bool,
 }
 
 impl Mouse {
 
     fn new() -> Mouse {
         return Mouse {
-            x: 0,
-            y: 0,
-            last_x: 0,
-            last_y: 0,
+            x: 0.0,
+            y: 0.0,
+            last_x: 0.0,
+            last_y: 0.0,
+            scale_x: 1.0,
+            scale_y: 1.0,
             scroll_x: 0.0,
             scroll_y: 0.0,
-            buttons: [Action::Up; (glfw::ffi::MOUSE_BUTTON_LAST + 1) as usize],
+            buttons: [Action::Up; MOUSE_BUTTON_COUNT],
+            click_interval: 0.5,
+            click_slop: 4.0,
+            clicks: [0; MOUSE_BUTTON_COUNT],
+            last_click_time: [f64::NEG_INFINITY; MOUSE_BUTTON_COUNT],
+            last_click_pos: [(0.0, 0.0); MOUSE_BUTTON_COUNT],
+            pressed: [false; MOUSE_BUTTON_COUNT],
+            drags: [Drag::new(); MOUSE_BUTTON_COUNT],
+            in_window: false,
+            hover_changed: false,
         }
     }
 
@@ -101,35 +197,114 @@ impl Mouse {
         self.last_y = self.y;
         self.scroll_x = 0.0;
         self.scroll_y = 0.0;
+        self.hover_changed = false;
 
         self.buttons.iter_mut().for_each(|button| button.update());
+        self.drags.iter_mut().for_each(|drag| drag.state.update());
+    }
+
+    // Events
+    // The slop is expressed in logical pixels
+    fn within_slop(&self, x: f64, y: f64) -> bool {
+        let dx = (self.x - x) / self.scale_x;
+        let dy = (self.y - y) / self.scale_y;
+        return dx * dx + dy * dy <= self.click_slop * self.click_slop;
+    }
+
+    pub(crate) fn press(&mut self, button: MouseButton, time: f64) {
+        let index = button as usize;
+        self.buttons[index] = Action::Pressed;
+
+        // Count successive clicks
+        let (last_x, last_y) = self.last_click_pos[index];
+        if time - self.last_click_time[index] <= self.click_interval && self.within_slop(last_x, last_y) {
+            self.clicks[index] += 1;
+        } else {
+            self.clicks[index] = 1;
+        }
+        self.last_click_time[index] = time;
+        self.last_click_pos[index] = (self.x, self.y);
+
+        // Prepare a drag, it starts once the cursor leaves the slop
+        self.pressed[index] = true;
+        self.drags[index].start_x = self.x;
+        self.drags[index].start_y = self.y;
+        self.drags[index].end_x = self.x;
+        self.drags[index].end_y = self.y;
+    }
+
+    pub(crate) fn release(&mut self, button: MouseButton) {
+        let index = button as usize;
+        self.buttons[index] = Action::Released;
+        self.pressed[index] = false;
+
+        if self.drags[index].state.is_dragging() {
+            self.drags[index].state = DragState::Ended;
+        }
+    }
+
+    pub(crate) fn move_to(&mut self, x: f64, y: f64) {
+        self.x = x;
+        self.y = y;
+
+        for index in 0..MOUSE_BUTTON_COUNT {
+            if !self.pressed[index] {
+                continue;
+            }
+
+            self.drags[index].end_x = x;
+            self.drags[index].end_y = y;
+
+            let (start_x, start_y) = (self.drags[index].start_x, self.drags[index].start_y);
+            if !self.drags[index].state.is_dragging() && !self.within_slop(start_x, start_y) {
+                self.drags[index].state = DragState::Started;
+            }
+        }
+    }
+
+    pub(crate) fn scroll(&mut self, scroll_x: f64, scroll_y: f64) {
+        self.scroll_x += scroll_x;
+        self.scroll_y += scroll_y;
+    }
+
+    pub(crate) fn hover(&mut self, in_window: bool) {
+        self.hover_changed = self.in_window != in_window;
+        self.in_window = in_window;
     }
 
     // Getters
-    pub fn get_x(&self) -> i32 {
+    pub fn get_x(&self) -> f64 {
         return self.x;
     }
 
-    pub fn get_y(&self) -> i32 {
+    pub fn get_y(&self) -> f64 {
         return self.y;
     }
 
-    pub fn get_last_x(&self) -> i32 {
+    pub fn get_last_x(&self) -> f64 {
         return self.last_x;
     }
 
-    pub fn get_last_y(&self) -> i32 {
+    pub fn get_last_y(&self) -> f64 {
         return self.last_y;
     }
 
-    pub fn get_dx(&self) -> i32 {
+    pub fn get_dx(&self) -> f64 {
         return self.last_x - self.x;
     }
 
-    pub fn get_dy(&self) -> i32 {
+    pub fn get_dy(&self) -> f64 {
         return self.last_y - self.y;
     }
 
+    pub fn get_logical_x(&self) -> f64 {
+        return self.x / self.scale_x;
+    }
+
+    pub fn get_logical_y(&self) -> f64 {
+        return self.y / self.scale_y;
+    }
+
     pub fn get_scroll_x(&self) -> f64 {
         return self.scroll_x;
     }
@@ -158,6 +333,34 @@ impl Mouse {
         return self.buttons[button as usize];
     }
 
+    pub fn get_click_count(&self, button: MouseButton) -> u32 {
+        return self.clicks[button as usize];
+    }
+
+    pub fn is_double_click(&self, button: MouseButton) -> bool {
+        return self.get_button(button).is_pressed() && self.clicks[button as usize] == 2;
+    }
+
+    pub fn is_triple_click(&self, button: MouseButton) -> bool {
+        return self.get_button(button).is_pressed() && self.clicks[button as usize] == 3;
+    }
+
+    pub fn get_drag(&self, button: MouseButton) -> Drag {
+        return self.drags[button as usize];
+    }
+
+    pub fn is_in_window(&self) -> bool {
+        return self.in_window;
+    }
+
+    pub fn has_entered(&self) -> bool {
+        return self.hover_changed && self.in_window;
+    }
+
+    pub fn has_left(&self) -> bool {
+        return self.hover_changed && !self.in_window;
+    }
+
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -217,4 +420,86 @@ impl Input {
         return &self.keyboard;
     }
 
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                              Tests                                             //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn click(mouse: &mut Mouse, time: f64) {
+        mouse.press(MouseButtonLeft, time);
+        mouse.release(MouseButtonLeft);
+        mouse.update();
+    }
+
+    #[test]
+    fn counts_successive_clicks() {
+        let mut mouse = Mouse::new();
+        click(&mut mouse, 0.0);
+        assert_eq!(mouse.get_click_count(MouseButtonLeft), 1);
+        click(&mut mouse, 0.2);
+        assert_eq!(mouse.get_click_count(MouseButtonLeft), 2);
+
+        // Double and triple clicks are only reported on the press
+        mouse.press(MouseButtonLeft, 0.4);
+        assert!(mouse.is_triple_click(MouseButtonLeft));
+        mouse.release(MouseButtonLeft);
+        mouse.update();
+        assert!(!mouse.is_triple_click(MouseButtonLeft));
+
+        // Too slow, the count restarts
+        click(&mut mouse, 1.5);
+        assert_eq!(mouse.get_click_count(MouseButtonLeft), 1);
+    }
+
+    #[test]
+    fn click_outside_slop_restarts_count() {
+        let mut mouse = Mouse::new();
+        click(&mut mouse, 0.0);
+        mouse.move_to(10.0, 0.0);
+        click(&mut mouse, 0.1);
+        assert_eq!(mouse.get_click_count(MouseButtonLeft), 1);
+
+        // The slop is in logical pixels, 6 framebuffer pixels at a scale of 2 is within 4
+        mouse.scale_x = 2.0;
+        mouse.scale_y = 2.0;
+        mouse.move_to(16.0, 0.0);
+        click(&mut mouse, 0.2);
+        assert_eq!(mouse.get_click_count(MouseButtonLeft), 2);
+    }
+
+    #[test]
+    fn drag_starts_after_threshold() {
+        let mut mouse = Mouse::new();
+        mouse.press(MouseButtonLeft, 0.0);
+        mouse.move_to(3.0, 0.0);
+        assert_eq!(mouse.get_drag(MouseButtonLeft).state, DragState::None);
+
+        mouse.move_to(10.0, 5.0);
+        let drag = mouse.get_drag(MouseButtonLeft);
+        assert!(drag.state.is_started());
+        assert_eq!((drag.get_dx(), drag.get_dy()), (10.0, 5.0));
+
+        mouse.update();
+        assert_eq!(mouse.get_drag(MouseButtonLeft).state, DragState::Dragging);
+
+        mouse.release(MouseButtonLeft);
+        assert!(mouse.get_drag(MouseButtonLeft).state.is_ended());
+        mouse.update();
+        assert_eq!(mouse.get_drag(MouseButtonLeft).state, DragState::None);
+    }
+
+    #[test]
+    fn release_without_drag_does_not_end() {
+        let mut mouse = Mouse::new();
+        mouse.press(MouseButtonLeft, 0.0);
+        mouse.release(MouseButtonLeft);
+        assert_eq!(mouse.get_drag(MouseButtonLeft).state, DragState::None);
+        assert!(mouse.get_button(MouseButtonLeft).is_released());
+    }
+
 }
\ No newline at end of file
@@ -1,4 +1,10 @@
 mod window;
 pub use window::*;
+mod clipboard;
+pub use clipboard::*;
+mod monitor;
+pub use monitor::*;
+mod geometry;
+pub use geometry::*;
 
-pub mod input;
\ No newline at end of file
+pub mod input;
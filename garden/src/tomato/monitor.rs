@@ -0,0 +1,133 @@
+use std::ptr::addr_of_mut;
+
+use glfw::{Callback, Error, Glfw};
+
+use crate::{critical, mem::MutRef};
+
+pub use glfw::VidMode;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                              GLFW                                              //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+static mut GLFW: Option<Glfw> = None;
+
+pub(crate) fn get_glfw() -> MutRef<Glfw> {
+    fn error_callback(error: Error, str: String, _: &()) {
+        match error {
+            // The clipboard doesn't contain text, handled by get_clipboard
+            Error::FormatUnavailable => {}
+            _ => critical!("Tomato:GLFW", "{}, {}", error, str),
+        }
+    }
+
+    unsafe {
+        return if let Some(glfw) = &mut *addr_of_mut!(GLFW) {
+            MutRef::from(glfw)
+        } else {
+            GLFW = Some(match glfw::init(Some(Callback {
+                f: error_callback,
+                data: (),
+            })) {
+                Ok(glfw) => glfw,
+                Err(err) => critical!("Tomato:GLFW", "{}", err),
+            });
+            MutRef::from((*addr_of_mut!(GLFW)).as_mut().unwrap())
+        }
+    }
+}
+
+// Wakes up the main thread waiting for events, safe to call from any thread once GLFW is initialized
+pub fn post_empty_event() {
+    unsafe {
+        glfw::ffi::glfwPostEmptyEvent();
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+//                                             Monitor                                            //
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Clone)]
+pub struct Monitor {
+    pub index: usize,
+    pub name: String,
+    pub primary: bool,
+    // Position of the monitor in screen coordinates
+    pub x: i32,
+    pub y: i32,
+    // Physical size in millimetres
+    pub physical_width: i32,
+    pub physical_height: i32,
+    pub scale_x: f32,
+    pub scale_y: f32,
+    // Work area, excludes task bars & menu bars
+    pub work_x: i32,
+    pub work_y: i32,
+    pub work_width: i32,
+    pub work_height: i32,
+    pub video_mode: Option<VidMode>,
+    pub video_modes: Vec<VidMode>,
+}
+
+impl Monitor {
+
+    fn from_glfw(index: usize, monitor: &glfw::Monitor) -> Monitor {
+        let (x, y) = monitor.get_pos();
+        let (physical_width, physical_height) = monitor.get_physical_size();
+        let (scale_x, scale_y) = monitor.get_content_scale();
+        let (work_x, work_y, work_width, work_height) = monitor.get_workarea();
+        return Monitor {
+            index,
+            name: monitor.get_name().unwrap_or_default(),
+            primary: false,
+            x,
+            y,
+            physical_width,
+            physical_height,
+            scale_x,
+            scale_y,
+            work_x,
+            work_y,
+            work_width,
+            work_height,
+            video_mode: monitor.get_video_mode(),
+            video_modes: monitor.get_video_modes(),
+        }
+    }
+
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        return x >= self.work_x && x < self.work_x + self.work_width &&
+            y >= self.work_y && y < self.work_y + self.work_height;
+    }
+
+    // Position of a window of the given size centered in the work area
+    pub fn center(&self, width: u32, height: u32) -> (i32, i32) {
+        return (
+            self.work_x + (self.work_width - width as i32) / 2,
+            self.work_y + (self.work_height - height as i32) / 2,
+        );
+    }
+
+}
+
+pub fn monitors() -> Vec<Monitor> {
+    let mut glfw = get_glfw();
+
+    let primary = glfw.with_primary_monitor(|_, monitor| monitor.map(|monitor| (monitor.get_name(), monitor.get_pos())));
+    return glfw.with_connected_monitors(|_, monitors| {
+        monitors.iter().enumerate().map(|(index, monitor)| {
+            let mut info = Monitor::from_glfw(index, monitor);
+            info.primary = primary == Some((monitor.get_name(), monitor.get_pos()));
+            info
+        }).collect()
+    });
+}
+
+pub fn primary_monitor() -> Option<Monitor> {
+    return monitors().into_iter().find(|monitor| monitor.primary);
+}
+
+pub fn get_monitor(index: usize) -> Option<Monitor> {
+    return monitors().into_iter().nth(index);
+}
@@ -2,25 +2,32 @@
 //                                          Configuration                                         //
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 
-use glfw::{Callback, Context, Error, SwapInterval, WindowEvent, WindowHint, WindowMode};
-use std::sync::mpsc::Receiver;
+pub use glfw::WindowEvent;
+use glfw::{Context, Modifiers, SwapInterval, WindowHint, WindowMode};
+use std::{path::PathBuf, sync::mpsc::Receiver};
 
 use crate::{critical, error};
 
-use super::input::{Action, Input};
+use super::{input::{Action, Input, Key, MouseButton}, get_local_clipboard, set_local_clipboard, get_glfw, monitors, get_monitor, primary_monitor, Monitor, WindowGeometry};
 
-pub struct WindowConfig<'a> {
+#[derive(Clone)]
+pub struct WindowConfig {
     pub width: u32,
     pub height: u32,
-    pub title: &'a str,
+    pub title: String,
+    // Monitor to center the window on (or to fill when fullscreen), the primary monitor if None
+    pub monitor: Option<usize>,
+    pub fullscreen: bool,
 }
 
-impl<'a> Default for WindowConfig<'a> {
+impl Default for WindowConfig {
     fn default() -> Self {
         return WindowConfig {
             width: 800,
             height: 600,
-            title: "Title",
+            title: "Title".to_string(),
+            monitor: None,
+            fullscreen: false,
         };
     }
 }
@@ -30,136 +37,256 @@ impl<'a> Default for WindowConfig<'a> {
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 
 pub struct Window {
-    window_ptr: glfw::Window,
-    events: Receiver<(f64, WindowEvent)>,
+    // None for headless windows
+    window_ptr: Option<glfw::Window>,
+    events: Option<Receiver<(f64, WindowEvent)>>,
+    injected: Vec<WindowEvent>,
     // Data
     width: u32,
     height: u32,
+    window_width: u32,
+    window_height: u32,
     scale_x: f32,
     scale_y: f32,
+    // Logical size at creation, used for the size ratio
+    initial_width: f32,
+    initial_height: f32,
     x: i32,
     y: i32,
+    // Geometry when not maximized
+    restored: (i32, i32, u32, u32),
     // Input
     input: Input,
     // Flags
     size_changed: bool,
     pos_changed: bool,
+    has_events: bool,
+    // Lifecycle
+    focus_changed: Option<bool>,
+    minimize_changed: Option<bool>,
+    dropped_files: Vec<PathBuf>,
+    close_requested: bool,
+    // Headless
+    time: f64,
+    closed: bool,
 }
 
 impl Window {
     pub fn new(config: WindowConfig) -> Window {
-        fn error_callback(error: Error, str: String, _: &()) {
-            critical!("Tomato:GLFW", "{}, {}", error, str);
-        }
-
         // Initialize GLFW
-        let mut glfw = match glfw::init(Some(Callback {
-            f: error_callback,
-            data: (),
-        })) {
-            Ok(glfw) => glfw,
-            Err(err) => critical!("Tomato:GLFW", "{}", err),
-        };
+        let mut glfw = get_glfw();
 
         // Set Window hints
         glfw.window_hint(WindowHint::Visible(false));
+        glfw.window_hint(WindowHint::ScaleToMonitor(true));
+
+        let window: Option<(glfw::Window, Receiver<(f64, WindowEvent)>)> = if config.fullscreen {
+            let index = config.monitor.or_else(|| primary_monitor().map(|monitor| monitor.index)).unwrap_or(0);
+            glfw.with_connected_monitors(|glfw, monitors| {
+                let mode = monitors.get(index).map_or(WindowMode::Windowed, WindowMode::FullScreen);
+                glfw.create_window(config.width, config.height, &config.title, mode)
+            })
+        } else {
+            glfw.create_window(config.width, config.height, &config.title, WindowMode::Windowed)
+        };
+        if let Some((window_ptr, events)) = window {
+            let mut window = Window::from_glfw(window_ptr, events, config.monitor);
 
-        let (width, height) = (config.width, config.height);
+            // Load GL functions
+            let window_ptr = window.window_ptr.as_mut().unwrap();
+            gl::load_with(|symbol| window_ptr.get_proc_address(symbol) as *const _);
 
-        // Calculate the x and y coordinates to center the window
-        let (x, y) = glfw.with_primary_monitor(|_, monitor| {
-            if let Some(monitor) = monitor {
-                if let Some(video_mode) = monitor.get_video_mode() {
-                    (
-                        ((video_mode.width - width) / 2) as i32,
-                        ((video_mode.height - height) / 2) as i32,
-                    )
-                } else {
-                    error!(
-                        "Tomato:GLFW",
-                        "Failed to get video mode from primary monitor!"
-                    );
-                    (0, 0)
-                }
-            } else {
-                error!("Tomato:GLFW", "Failed to find primary monitor!");
-                (0, 0)
-            }
-        });
+            return window;
+        } else {
+            critical!("Tomato:GLFW", "Failed to create window!");
+        }
+    }
+
+    // Window without a GLFW window or GL context, time only advances through advance_time
+    pub fn headless(config: WindowConfig) -> Window {
+        return Window {
+            window_ptr: None,
+            events: None,
+            injected: Vec::new(),
+            width: config.width,
+            height: config.height,
+            window_width: config.width,
+            window_height: config.height,
+            scale_x: 1.0,
+            scale_y: 1.0,
+            initial_width: config.width as f32,
+            initial_height: config.height as f32,
+            x: 0,
+            y: 0,
+            restored: (0, 0, config.width, config.height),
+            input: Input::new(),
+            size_changed: true,
+            pos_changed: true,
+            has_events: true,
+            focus_changed: None,
+            minimize_changed: None,
+            dropped_files: Vec::new(),
+            close_requested: false,
+            time: 0.0,
+            closed: false,
+        };
+    }
+
+    pub fn new_shared(&self, config: WindowConfig) -> Window {
+        let window_ptr = if let Some(window_ptr) = &self.window_ptr {
+            window_ptr
+        } else {
+            return Window::headless(config);
+        };
 
         let window: Option<(glfw::Window, Receiver<(f64, WindowEvent)>)> =
-            glfw.create_window(width, height, config.title, WindowMode::Windowed);
-        if let Some((mut window_ptr, events)) = window {
-            // Center the window
-            window_ptr.set_pos(x, y);
+            window_ptr.create_shared(config.width, config.height, &config.title, WindowMode::Windowed);
+        if let Some((window_ptr, events)) = window {
+            return Window::from_glfw(window_ptr, events, config.monitor);
+        } else {
+            critical!("Tomato:GLFW", "Failed to create shared window!");
+        }
+    }
 
-            // Enable all events
-            window_ptr.set_all_polling(true);
+    fn from_glfw(mut window_ptr: glfw::Window, events: Receiver<(f64, WindowEvent)>, monitor: Option<usize>) -> Window {
+        // The window may have been scaled to the monitor's content scale
+        let (window_width, window_height) = window_ptr.get_size();
+        let (width, height) = (window_width as u32, window_height as u32);
 
-            // Make current
-            window_ptr.make_current();
-            gl::load_with(|symbol| window_ptr.get_proc_address(symbol) as *const _);
+        // Calculate the x and y coordinates to center the window
+        let monitor = if let Some(index) = monitor {
+            get_monitor(index).or_else(|| {
+                error!("Tomato:GLFW", "Failed to find monitor {}!", index);
+                primary_monitor()
+            })
+        } else {
+            primary_monitor()
+        };
+        let (x, y) = if let Some(monitor) = monitor {
+            monitor.center(width, height)
+        } else {
+            error!("Tomato:GLFW", "Failed to find primary monitor!");
+            (0, 0)
+        };
 
-            return Window {
-                window_ptr,
-                events,
-                width: config.width,
-                height: config.height,
-                scale_x: 1.0,
-                scale_y: 1.0,
-                x,
-                y,
-                input: Input::new(),
-                size_changed: true,
-                pos_changed: true,
-            };
+        // Center the window, fullscreen windows are placed by their monitor
+        let fullscreen = window_ptr.with_window_mode(|mode| matches!(mode, WindowMode::FullScreen(_)));
+        let (x, y) = if fullscreen {
+            window_ptr.get_pos()
         } else {
-            critical!("Tomato:GLFW", "Failed to create window!");
-        }
+            window_ptr.set_pos(x, y);
+            (x, y)
+        };
+
+        // Enable all events
+        window_ptr.set_all_polling(true);
+
+        // Make current
+        window_ptr.make_current();
+
+        // Retrieve the framebuffer size & content scale
+        let (framebuffer_width, framebuffer_height) = window_ptr.get_framebuffer_size();
+        let (scale_x, scale_y) = window_ptr.get_content_scale();
+        let mut input = Input::new();
+        input.mouse.scale_x = scale_x as f64;
+        input.mouse.scale_y = scale_y as f64;
+
+        return Window {
+            window_ptr: Some(window_ptr),
+            events: Some(events),
+            injected: Vec::new(),
+            width: framebuffer_width as u32,
+            height: framebuffer_height as u32,
+            window_width: width,
+            window_height: height,
+            scale_x,
+            scale_y,
+            initial_width: framebuffer_width as f32 / scale_x,
+            initial_height: framebuffer_height as f32 / scale_y,
+            x,
+            y,
+            restored: (x, y, width, height),
+            input,
+            size_changed: true,
+            pos_changed: true,
+            has_events: true,
+            focus_changed: None,
+            minimize_changed: None,
+            dropped_files: Vec::new(),
+            close_requested: false,
+            time: 0.0,
+            closed: false,
+        };
     }
 
     pub fn update(&mut self) {
         // Reset flags
         self.size_changed = false;
         self.pos_changed = false;
+        self.has_events = false;
+        self.focus_changed = None;
+        self.minimize_changed = None;
+        self.dropped_files.clear();
+        self.close_requested = false;
 
         // Update Input
         self.input.update();
 
         // Update
-        self.window_ptr.glfw.poll_events();
+        let mut events = Vec::new();
+        if let (Some(window_ptr), Some(receiver)) = (&mut self.window_ptr, &self.events) {
+            window_ptr.glfw.poll_events();
+            events.extend(glfw::flush_messages(receiver));
+        }
+        let time = self.get_time();
+        events.extend(self.injected.drain(..).map(|event| (time, event)));
 
-        for (_, event) in glfw::flush_messages(&self.events) {
+        for (time, event) in events {
+            self.has_events = true;
             match event {
                 WindowEvent::Pos(x, y) => {
                     self.x = x;
                     self.y = y;
                     self.pos_changed = true;
+                    if !self.is_maximized() {
+                        self.restored.0 = x;
+                        self.restored.1 = y;
+                    }
+                }
+                WindowEvent::Size(width, height) => {
+                    self.window_width = width as u32;
+                    self.window_height = height as u32;
+                    if !self.is_maximized() {
+                        self.restored.2 = width as u32;
+                        self.restored.3 = height as u32;
+                    }
                 }
                 WindowEvent::FramebufferSize(width, height) => {
-                    self.scale_x *= width as f32 / self.width as f32;
-                    self.scale_y *= height as f32 / self.height as f32;
                     self.width = width as u32;
                     self.height = height as u32;
                     self.size_changed = true;
                 }
+                WindowEvent::ContentScale(scale_x, scale_y) => {
+                    self.scale_x = scale_x;
+                    self.scale_y = scale_y;
+                    self.input.mouse.scale_x = scale_x as f64;
+                    self.input.mouse.scale_y = scale_y as f64;
+                    self.size_changed = true;
+                }
                 WindowEvent::MouseButton(button, action, _) => match action {
-                    glfw::Action::Release => {
-                        self.input.mouse.buttons[button as usize] = Action::Released
-                    }
-                    glfw::Action::Press => {
-                        self.input.mouse.buttons[button as usize] = Action::Pressed
-                    }
+                    glfw::Action::Release => self.input.mouse.release(button),
+                    glfw::Action::Press => self.input.mouse.press(button, time),
                     _ => {}
                 },
                 WindowEvent::CursorPos(mx, my) => {
-                    self.input.mouse.x = mx as i32;
-                    self.input.mouse.y = my as i32;
-                }
-                WindowEvent::Scroll(sx, sy) => {
-                    self.input.mouse.scroll_x = sx;
-                    self.input.mouse.scroll_y = sy;
+                    // Map the cursor from window coordinates into framebuffer space
+                    self.input.mouse.move_to(
+                        mx * self.width as f64 / self.window_width.max(1) as f64,
+                        my * self.height as f64 / self.window_height.max(1) as f64,
+                    );
                 }
+                WindowEvent::CursorEnter(entered) => self.input.mouse.hover(entered),
+                WindowEvent::Scroll(sx, sy) => self.input.mouse.scroll(sx, sy),
                 WindowEvent::Key(key, _, action, _) => match action {
                     glfw::Action::Release => {
                         self.input.keyboard.keys[key as usize] = Action::Released
@@ -169,14 +296,63 @@ impl Window {
                 },
                 WindowEvent::Char(_) => {}
                 WindowEvent::CharModifiers(_, _) => {}
-                WindowEvent::FileDrop(_) => {}
+                WindowEvent::FileDrop(paths) => self.dropped_files.extend(paths),
+                WindowEvent::Focus(focused) => self.focus_changed = Some(focused),
+                WindowEvent::Iconify(minimized) => self.minimize_changed = Some(minimized),
+                WindowEvent::Close => {
+                    // GLFW already flagged its window, headless windows close here
+                    self.closed = true;
+                    self.close_requested = true;
+                }
                 _ => {}
             }
         }
     }
 
+    // Blocks until an event is received or the timeout (in seconds) expires
+    pub fn wait_events_timeout(&mut self, timeout: f64) {
+        if let Some(window_ptr) = &mut self.window_ptr {
+            window_ptr.glfw.wait_events_timeout(timeout);
+        }
+    }
+
     pub fn swap_buffers(&mut self) {
-        self.window_ptr.swap_buffers();
+        if let Some(window_ptr) = &mut self.window_ptr {
+            window_ptr.swap_buffers();
+        }
+    }
+
+    pub fn make_current(&mut self) {
+        if let Some(window_ptr) = &mut self.window_ptr {
+            window_ptr.make_current();
+        }
+    }
+
+    // Queue an event, handled by the next update like any other event
+    pub fn inject_event(&mut self, event: WindowEvent) {
+        self.injected.push(event);
+    }
+
+    pub fn inject_key(&mut self, key: Key, pressed: bool) {
+        let action = if pressed { glfw::Action::Press } else { glfw::Action::Release };
+        self.inject_event(WindowEvent::Key(key, 0, action, Modifiers::empty()));
+    }
+
+    pub fn inject_mouse_button(&mut self, button: MouseButton, pressed: bool) {
+        let action = if pressed { glfw::Action::Press } else { glfw::Action::Release };
+        self.inject_event(WindowEvent::MouseButton(button, action, Modifiers::empty()));
+    }
+
+    pub fn advance_time(&mut self, delta: f64) {
+        if self.window_ptr.is_some() {
+            error!("Tomato", "Cannot advance the time of a window that isn't headless!");
+            return;
+        }
+        self.time += delta;
+    }
+
+    pub fn is_headless(&self) -> bool {
+        return self.window_ptr.is_none();
     }
 
     // Getters
@@ -188,6 +364,22 @@ impl Window {
         return self.height;
     }
 
+    pub fn get_window_width(&self) -> u32 {
+        return self.window_width;
+    }
+
+    pub fn get_window_height(&self) -> u32 {
+        return self.window_height;
+    }
+
+    pub fn get_logical_width(&self) -> f32 {
+        return self.width as f32 / self.scale_x;
+    }
+
+    pub fn get_logical_height(&self) -> f32 {
+        return self.height as f32 / self.scale_y;
+    }
+
     pub fn get_scale_x(&self) -> f32 {
         return self.scale_x;
     }
@@ -196,6 +388,15 @@ impl Window {
         return self.scale_y;
     }
 
+    // Ratio of the current logical size to the size at creation
+    pub fn get_size_ratio_x(&self) -> f32 {
+        return self.get_logical_width() / self.initial_width;
+    }
+
+    pub fn get_size_ratio_y(&self) -> f32 {
+        return self.get_logical_height() / self.initial_height;
+    }
+
     pub fn get_x(&self) -> i32 {
         return self.x;
     }
@@ -205,7 +406,7 @@ impl Window {
     }
 
     pub fn should_close(&self) -> bool {
-        return self.window_ptr.should_close();
+        return self.window_ptr.as_ref().map_or(self.closed, |window_ptr| window_ptr.should_close());
     }
 
     pub fn input(&self) -> &Input {
@@ -213,32 +414,44 @@ impl Window {
     }
 
     pub fn set_vsync(&mut self, enable: bool) {
-        self.window_ptr.glfw.set_swap_interval(
-            enable
-                .then(|| SwapInterval::Sync(1))
-                .unwrap_or(SwapInterval::None),
-        );
+        if let Some(window_ptr) = &mut self.window_ptr {
+            window_ptr.glfw.set_swap_interval(
+                enable
+                    .then(|| SwapInterval::Sync(1))
+                    .unwrap_or(SwapInterval::None),
+            );
+        }
     }
 
     // Setters
+    // The size is in window coordinates, the framebuffer size is updated by the next event
     pub fn set_size(&mut self, width: u32, height: u32) {
-        self.width = width;
-        self.height = height;
-        self.window_ptr.set_size(width as i32, height as i32);
+        self.window_width = width;
+        self.window_height = height;
+        if let Some(window_ptr) = &mut self.window_ptr {
+            window_ptr.set_size(width as i32, height as i32);
+        } else {
+            // No framebuffer event will follow
+            self.width = width;
+            self.height = height;
+            self.size_changed = true;
+        }
     }
 
     pub fn set_width(&mut self, width: u32) {
-        self.set_size(width, self.height);
+        self.set_size(width, self.window_height);
     }
 
     pub fn set_height(&mut self, height: u32) {
-        self.set_size(self.width, height);
+        self.set_size(self.window_width, height);
     }
 
     pub fn set_pos(&mut self, x: i32, y: i32) {
         self.x = x;
         self.y = y;
-        self.window_ptr.set_pos(x, y);
+        if let Some(window_ptr) = &mut self.window_ptr {
+            window_ptr.set_pos(x, y);
+        }
     }
 
     pub fn set_x(&mut self, x: i32) {
@@ -249,23 +462,152 @@ impl Window {
         self.set_pos(self.x, y);
     }
 
+    // Placement
+    pub fn center(&mut self) {
+        if let Some(monitor) = primary_monitor() {
+            self.center_on(&monitor);
+        } else {
+            error!("Tomato:GLFW", "Failed to find primary monitor!");
+        }
+    }
+
+    pub fn center_on_monitor(&mut self, index: usize) {
+        if let Some(monitor) = get_monitor(index) {
+            self.center_on(&monitor);
+        } else {
+            error!("Tomato:GLFW", "Failed to find monitor {}!", index);
+        }
+    }
+
+    pub fn center_on(&mut self, monitor: &Monitor) {
+        let (x, y) = monitor.center(self.window_width, self.window_height);
+        self.set_pos(x, y);
+    }
+
+    pub fn is_maximized(&self) -> bool {
+        return self.window_ptr.as_ref().is_some_and(|window_ptr| window_ptr.is_maximized());
+    }
+
+    pub fn maximize(&mut self) {
+        if let Some(window_ptr) = &mut self.window_ptr {
+            window_ptr.maximize();
+        }
+    }
+
+    pub fn restore(&mut self) {
+        if let Some(window_ptr) = &mut self.window_ptr {
+            window_ptr.restore();
+        }
+    }
+
+    pub fn get_geometry(&self) -> WindowGeometry {
+        let (x, y, width, height) = self.restored;
+        return WindowGeometry {
+            x,
+            y,
+            width,
+            height,
+            maximized: self.is_maximized(),
+            monitor: self.get_monitor().map(|monitor| monitor.name),
+        }
+    }
+
+    pub fn set_geometry(&mut self, mut geometry: WindowGeometry) {
+        geometry.clamp(&monitors());
+        self.set_size(geometry.width, geometry.height);
+        self.set_pos(geometry.x, geometry.y);
+        self.restored = (geometry.x, geometry.y, geometry.width, geometry.height);
+        if geometry.maximized {
+            self.maximize();
+        }
+    }
+
+    // Monitor containing the center of the window
+    pub fn get_monitor(&self) -> Option<Monitor> {
+        if self.is_headless() {
+            return None;
+        }
+        let center_x = self.x + self.window_width as i32 / 2;
+        let center_y = self.y + self.window_height as i32 / 2;
+        return monitors().into_iter().find(|monitor| monitor.contains(center_x, center_y));
+    }
+
     pub fn close(&mut self) {
-        self.window_ptr.set_should_close(true);
+        if let Some(window_ptr) = &mut self.window_ptr {
+            window_ptr.set_should_close(true);
+        } else {
+            self.closed = true;
+        }
     }
 
     pub fn show(&mut self) {
-        self.window_ptr.show();
+        if let Some(window_ptr) = &mut self.window_ptr {
+            window_ptr.show();
+        }
     }
 
     pub fn hide(&mut self) {
-        self.window_ptr.hide();
+        if let Some(window_ptr) = &mut self.window_ptr {
+            window_ptr.hide();
+        }
+    }
+
+    pub fn get_clipboard(&self) -> Option<String> {
+        return match &self.window_ptr {
+            Some(window_ptr) => window_ptr.get_clipboard_string(),
+            None => get_local_clipboard(),
+        };
+    }
+
+    pub fn set_clipboard(&mut self, text: &str) {
+        match &mut self.window_ptr {
+            Some(window_ptr) => window_ptr.set_clipboard_string(text),
+            None => set_local_clipboard(text),
+        }
     }
 
     pub fn get_time(&self) -> f64 {
-        return self.window_ptr.glfw.get_time();
+        return self.window_ptr.as_ref().map_or(self.time, |window_ptr| window_ptr.glfw.get_time());
+    }
+
+    // Lifecycle, reset on every update
+    pub fn focus_changed(&self) -> Option<bool> {
+        return self.focus_changed;
+    }
+
+    pub fn minimize_changed(&self) -> Option<bool> {
+        return self.minimize_changed;
+    }
+
+    pub fn get_dropped_files(&self) -> &[PathBuf] {
+        return &self.dropped_files;
+    }
+
+    pub fn close_requested(&self) -> bool {
+        return self.close_requested;
+    }
+
+    // Keep the window open after a close request
+    pub fn cancel_close(&mut self) {
+        if let Some(window_ptr) = &mut self.window_ptr {
+            window_ptr.set_should_close(false);
+        }
+        self.closed = false;
+    }
+
+    pub fn is_focused(&self) -> bool {
+        return self.window_ptr.as_ref().is_none_or(|window_ptr| window_ptr.is_focused());
+    }
+
+    pub fn is_minimized(&self) -> bool {
+        return self.window_ptr.as_ref().is_some_and(|window_ptr| window_ptr.is_iconified());
     }
 
     pub fn size_changed(&self) -> bool {
         return self.size_changed;
     }
+
+    pub fn has_events(&self) -> bool {
+        return self.has_events;
+    }
 }
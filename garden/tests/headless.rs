@@ -0,0 +1,87 @@
+use garden::{
+    app::{App, AppConfiguration, FixedTimestep, IState},
+    assets::{Axis, Color},
+    ui::{
+        components::BackgroundComponent, get_system, set_scene, ConstraintHeight, ConstraintWidth,
+        ConstraintX, ConstraintY, ElementRef, IUIElement,
+    },
+};
+
+struct CounterState {
+    updates: u32,
+    fixed_updates: u32,
+    elapsed: f64,
+}
+
+impl IState for CounterState {
+    fn init(&mut self) {
+        let mut entity = get_system().get_entity_mut("Panel".to_string());
+        let mut constraints = entity.get_constraints();
+        constraints.constraint_x = ConstraintX::pixel_in_left(10.0, ElementRef::Screen);
+        constraints.constraint_y = ConstraintY::pixel_in_top(20.0, ElementRef::Screen);
+        constraints.constraint_width = ConstraintWidth::percent(0.5, (Axis::X, ElementRef::Screen));
+        constraints.constraint_height = ConstraintHeight::pixel(50.0);
+        let mut scene = entity.get_scene();
+        scene.0 = Some("Main".to_string());
+        entity.add_ui_component(BackgroundComponent::new(Color::red()));
+
+        set_scene(Some("Main".to_string()));
+    }
+
+    fn open(&mut self) {}
+
+    fn update(&mut self, delta: f64) {
+        self.updates += 1;
+        self.elapsed += delta;
+    }
+
+    fn fixed_update(&mut self, _delta: f64) {
+        self.fixed_updates += 1;
+    }
+
+    fn render(&mut self, _alpha: f64) {
+        App::get().renderer().r2d.fill_rect(self.updates as f32 * 10.0, 0.0, 5.0, 5.0, Color::blue());
+    }
+
+    fn close(&mut self) {}
+
+    fn dispose(&mut self) {}
+}
+
+// The app is a singleton, so the whole scenario runs in a single test
+#[test]
+fn headless_frames() {
+    let config = AppConfiguration {
+        fixed_timestep: Some(FixedTimestep::new(60)),
+        ..AppConfiguration::default()
+    };
+
+    App::get().state_manager().register(CounterState { updates: 0, fixed_updates: 0, elapsed: 0.0 });
+    App::get().state_manager().open::<CounterState>();
+    App::get().setup_headless(config);
+    assert!(App::get().is_headless());
+
+    App::get().run_frames(3, 1.0 / 30.0);
+
+    // State values follow the simulated time
+    let state = App::get().state_manager().get_state::<CounterState>().unwrap();
+    assert_eq!(state.updates, 3);
+    assert_eq!(state.fixed_updates, 6);
+    assert!((state.elapsed - 0.1).abs() < 1e-9);
+    assert_eq!(App::get().time().get_frame_count(), 3);
+
+    // Layout of the UI element
+    let mut panel = get_system().get_entity_mut("Panel".to_string());
+    let mut constraints = panel.get_constraints();
+    assert_eq!((constraints.get_x(), constraints.get_y()), (10.0, 20.0));
+    assert_eq!((constraints.get_width(), constraints.get_height()), (400.0, 50.0));
+
+    // Only the draw calls of the last frame are kept, the state's rect & the panel background
+    let renderer = App::get().renderer();
+    let draw_calls = renderer.r2d.get_draw_calls();
+    assert_eq!(draw_calls.len(), 2);
+    assert!(draw_calls.iter().any(|call| call.shape.x == 30.0 && call.shape.width == 5.0));
+    assert!(draw_calls.iter().any(|call| {
+        (call.shape.x, call.shape.y, call.shape.width, call.shape.height) == (10.0, 20.0, 400.0, 50.0)
+    }));
+}
\ No newline at end of file
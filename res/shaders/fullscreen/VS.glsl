@@ -0,0 +1,15 @@
+#version 420 core
+
+out vec2 fUV;
+
+void main() {
+    // Fullscreen quad, no vertex buffer needed
+    vec2 positions[6] = vec2[](
+        vec2(-1.0, -1.0), vec2(1.0, -1.0), vec2(1.0, 1.0),
+        vec2(-1.0, -1.0), vec2(1.0, 1.0), vec2(-1.0, 1.0)
+    );
+    vec2 pos = positions[gl_VertexID];
+
+    fUV = pos * 0.5 + 0.5;
+    gl_Position = vec4(pos, 0.0, 1.0);
+}
\ No newline at end of file
@@ -0,0 +1,27 @@
+#version 420 core
+
+in vec2 fUV;
+
+out vec4 oColor;
+
+uniform sampler2D uScene;
+uniform vec2 uTexelSize;
+uniform vec2 uDirection;
+uniform float uRadius;
+
+void main() {
+    // Gaussian kernel, sigma is a third of the radius
+    float sigma = max(uRadius / 3.0, 0.0001);
+    int samples = int(ceil(uRadius));
+
+    vec4 color = texture(uScene, fUV);
+    float total = 1.0;
+    for (int i = 1; i <= samples; i++) {
+        float weight = exp(-float(i * i) / (2.0 * sigma * sigma));
+        vec2 offset = uDirection * uTexelSize * float(i);
+        color += texture(uScene, fUV + offset) * weight;
+        color += texture(uScene, fUV - offset) * weight;
+        total += weight * 2.0;
+    }
+    oColor = color / total;
+}
\ No newline at end of file
@@ -0,0 +1,22 @@
+#version 420 core
+
+in vec2 fUV;
+
+out vec4 oColor;
+
+uniform sampler2D uScene;
+uniform float uBrightness;
+uniform float uContrast;
+uniform float uSaturation;
+uniform vec4 uTint;
+
+void main() {
+    vec4 scene = texture(uScene, fUV);
+    vec3 color = scene.rgb + uBrightness;
+    color = (color - 0.5) * uContrast + 0.5;
+
+    float luma = dot(color, vec3(0.2126, 0.7152, 0.0722));
+    color = mix(vec3(luma), color, uSaturation);
+
+    oColor = vec4(clamp(color * uTint.rgb, 0.0, 1.0), scene.a);
+}
\ No newline at end of file
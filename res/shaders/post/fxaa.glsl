@@ -0,0 +1,44 @@
+#version 420 core
+
+in vec2 fUV;
+
+out vec4 oColor;
+
+uniform sampler2D uScene;
+uniform vec2 uTexelSize;
+
+const float REDUCE_MIN = 1.0 / 128.0;
+const float REDUCE_MUL = 1.0 / 8.0;
+const float SPAN_MAX = 8.0;
+
+float luma(vec3 color) {
+    return dot(color, vec3(0.299, 0.587, 0.114));
+}
+
+void main() {
+    float lumaNW = luma(texture(uScene, fUV + vec2(-1.0, -1.0) * uTexelSize).rgb);
+    float lumaNE = luma(texture(uScene, fUV + vec2(1.0, -1.0) * uTexelSize).rgb);
+    float lumaSW = luma(texture(uScene, fUV + vec2(-1.0, 1.0) * uTexelSize).rgb);
+    float lumaSE = luma(texture(uScene, fUV + vec2(1.0, 1.0) * uTexelSize).rgb);
+    vec4 center = texture(uScene, fUV);
+    float lumaM = luma(center.rgb);
+
+    float lumaMin = min(lumaM, min(min(lumaNW, lumaNE), min(lumaSW, lumaSE)));
+    float lumaMax = max(lumaM, max(max(lumaNW, lumaNE), max(lumaSW, lumaSE)));
+
+    // Blur along the edge
+    vec2 dir = vec2(-((lumaNW + lumaNE) - (lumaSW + lumaSE)), (lumaNW + lumaSW) - (lumaNE + lumaSE));
+    float dirReduce = max((lumaNW + lumaNE + lumaSW + lumaSE) * 0.25 * REDUCE_MUL, REDUCE_MIN);
+    float rcpDirMin = 1.0 / (min(abs(dir.x), abs(dir.y)) + dirReduce);
+    dir = clamp(dir * rcpDirMin, vec2(-SPAN_MAX), vec2(SPAN_MAX)) * uTexelSize;
+
+    vec3 rgbA = 0.5 * (
+        texture(uScene, fUV + dir * (1.0 / 3.0 - 0.5)).rgb +
+        texture(uScene, fUV + dir * (2.0 / 3.0 - 0.5)).rgb);
+    vec3 rgbB = rgbA * 0.5 + 0.25 * (
+        texture(uScene, fUV + dir * -0.5).rgb +
+        texture(uScene, fUV + dir * 0.5).rgb);
+
+    float lumaB = luma(rgbB);
+    oColor = vec4((lumaB < lumaMin || lumaB > lumaMax) ? rgbA : rgbB, center.a);
+}
\ No newline at end of file
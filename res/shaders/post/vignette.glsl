@@ -0,0 +1,19 @@
+#version 420 core
+
+in vec2 fUV;
+
+out vec4 oColor;
+
+uniform sampler2D uScene;
+uniform float uIntensity;
+uniform float uRadius;
+uniform float uSoftness;
+
+void main() {
+    vec4 scene = texture(uScene, fUV);
+    // 1.0 at the corners
+    float dist = length(fUV - 0.5) / length(vec2(0.5));
+    float vignette = smoothstep(uRadius, uRadius + uSoftness, dist);
+
+    oColor = vec4(scene.rgb * (1.0 - vignette * uIntensity), scene.a);
+}
\ No newline at end of file
@@ -9,10 +9,13 @@ flat in float fRoundness;
 out vec4 oColor;
 
 uniform vec2 uScreenSize;
+uniform vec2 uViewOrigin;
+uniform mat4 uInverseView;
 
 void main() {
-    // Compute pixel position
-    vec2 pos = vec2(gl_FragCoord.x, uScreenSize.y - gl_FragCoord.y);
+    // Compute pixel position, in world space when drawn through a camera
+    vec2 pos = vec2(gl_FragCoord.x, uScreenSize.y - gl_FragCoord.y) - uViewOrigin;
+    pos = (uInverseView * vec4(pos, 0.0, 1.0)).xy;
 
     // Compute alpha level
     float alpha = -1.0;
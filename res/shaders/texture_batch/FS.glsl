@@ -0,0 +1,12 @@
+#version 420 core
+
+in vec2 fUV;
+in vec4 fTint;
+
+out vec4 oColor;
+
+uniform sampler2D uTexture;
+
+void main() {
+    oColor = texture(uTexture, fUV) * fTint;
+}
\ No newline at end of file
@@ -0,0 +1,16 @@
+#version 420 core
+
+layout (location = 0) in vec2 aPos;
+layout (location = 1) in vec2 aUV;
+layout (location = 2) in vec4 aTint;
+
+out vec2 fUV;
+out vec4 fTint;
+
+uniform mat4 uProjection;
+
+void main() {
+    fUV = aUV;
+    fTint = aTint;
+    gl_Position = uProjection * vec4(aPos, 0.0, 1.0);
+}
\ No newline at end of file
@@ -0,0 +1,32 @@
+#version 420 core
+
+in vec2 fUV;
+
+out vec4 oColor;
+
+uniform sampler2D uFrom;
+uniform sampler2D uTo;
+uniform float uProgress;
+uniform int uMode; // 0 = Fade, 1 = Crossfade, 2 = Slide
+uniform vec4 uColor;
+uniform vec2 uDirection;
+
+void main() {
+    if (uMode == 0) { // Fade through color
+        if (uProgress < 0.5) {
+            oColor = mix(texture(uFrom, fUV), uColor, uProgress * 2.0);
+        } else {
+            oColor = mix(uColor, texture(uTo, fUV), (uProgress - 0.5) * 2.0);
+        }
+    } else if (uMode == 1) { // Crossfade
+        oColor = mix(texture(uFrom, fUV), texture(uTo, fUV), uProgress);
+    } else { // Slide, the new state pushes the old one out
+        vec2 fromUV = fUV - uDirection * uProgress;
+        vec2 toUV = fUV - uDirection * (uProgress - 1.0);
+        if (all(greaterThanEqual(fromUV, vec2(0.0))) && all(lessThanEqual(fromUV, vec2(1.0)))) {
+            oColor = texture(uFrom, fromUV);
+        } else {
+            oColor = texture(uTo, toUV);
+        }
+    }
+}